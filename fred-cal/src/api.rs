@@ -3,12 +3,15 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
-use crate::models::CalendarData;
-use crate::sync::SyncManager;
+use crate::models::{CalendarData, Journal};
+use crate::recurrence::RecurrenceConfig;
+use crate::reminders::{DEFAULT_SNOOZE_MINUTES, DeliveredReminder, ReminderScheduler};
+use crate::subtasks::{TodoNode, build_todo_tree};
+use crate::sync::{SyncManager, SyncWindow};
 use axum::{
     Router,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
 };
@@ -27,6 +30,13 @@ pub struct CombinedResponse {
     pub events: Vec<crate::models::CalendarEvent>,
     pub todos: Vec<crate::models::Todo>,
     pub last_sync: DateTime<Utc>,
+    /// UIDs of recurring series cut short by the per-series instance cap
+    #[serde(default)]
+    pub truncated_series: Vec<String>,
+    /// The requested range reaches outside the synced window, so items
+    /// there may be missing
+    #[serde(default)]
+    pub outside_sync_window: bool,
 }
 
 /// API response for calendar-only data
@@ -34,6 +44,13 @@ pub struct CombinedResponse {
 pub struct CalendarsResponse {
     pub events: Vec<crate::models::CalendarEvent>,
     pub last_sync: DateTime<Utc>,
+    /// UIDs of recurring series cut short by the per-series instance cap
+    #[serde(default)]
+    pub truncated_series: Vec<String>,
+    /// The requested range reaches outside the synced window, so items
+    /// there may be missing
+    #[serde(default)]
+    pub outside_sync_window: bool,
 }
 
 /// API response for todos-only data
@@ -41,6 +58,30 @@ pub struct CalendarsResponse {
 pub struct TodosResponse {
     pub todos: Vec<crate::models::Todo>,
     pub last_sync: DateTime<Utc>,
+    /// UIDs of recurring series cut short by the per-series instance cap
+    #[serde(default)]
+    pub truncated_series: Vec<String>,
+    /// The requested range reaches outside the synced window, so items
+    /// there may be missing
+    #[serde(default)]
+    pub outside_sync_window: bool,
+}
+
+/// Query parameters for endpoints returning events
+#[derive(Debug, Default, Deserialize)]
+struct EventFilter {
+    /// Leave out events the current user has declined
+    #[serde(default)]
+    exclude_declined: bool,
+}
+
+impl EventFilter {
+    /// Drop the events this filter excludes
+    fn apply(&self, events: &mut Vec<crate::models::CalendarEvent>) {
+        if self.exclude_declined {
+            events.retain(|event| !event.is_declined());
+        }
+    }
 }
 
 /// API error response
@@ -54,23 +95,58 @@ struct ErrorResponse {
 pub struct AppState {
     pub data: Arc<RwLock<CalendarData>>,
     pub sync_manager: Option<Arc<SyncManager>>,
+    pub recurrence: RecurrenceConfig,
+    pub reminders: Option<Arc<ReminderScheduler>>,
+    pub sync_window: SyncWindow,
+}
+
+impl AppState {
+    /// Whether `start..end` reaches outside the time range kept in sync
+    fn outside_sync_window(&self, start: DateTime<Local>, end: DateTime<Local>) -> bool {
+        !self.sync_window.covers(
+            start.with_timezone(&Utc),
+            end.with_timezone(&Utc),
+            Utc::now(),
+        )
+    }
 }
 
 /// Create the API router with all endpoints
 pub fn create_router(
     data: Arc<RwLock<CalendarData>>,
     sync_manager: Option<Arc<SyncManager>>,
+    recurrence: RecurrenceConfig,
+    reminders: Option<Arc<ReminderScheduler>>,
 ) -> Router {
-    let state = AppState { data, sync_manager };
+    let sync_window = sync_manager
+        .as_ref()
+        .map(|sync_manager| sync_manager.sync_window())
+        .unwrap_or_default();
+    let state = AppState {
+        data,
+        sync_manager,
+        recurrence,
+        reminders,
+        sync_window,
+    };
 
     Router::new()
         .route("/api/get_today", get(get_today))
         .route("/api/get_today_calendars", get(get_today_calendars))
         .route("/api/get_today_todos", get(get_today_todos))
+        .route("/api/todos/tree", get(get_todo_tree))
+        .route("/api/journals", get(get_journals))
         .route("/api/get_date_range/{range}", get(get_date_range))
         .route("/api/debug/events", get(debug_events))
         .route("/api/health", get(health_check))
         .route("/api/sync", post(trigger_sync))
+        .route("/api/reminders", get(get_reminders))
+        .route(
+            "/api/reminders/{id}/acknowledge",
+            post(acknowledge_reminder),
+        )
+        .route("/api/reminders/{id}/snooze", post(snooze_reminder))
+        .route("/api/objects/{uid}/ics", get(get_object_ics))
         .with_state(state)
         .layer(
             TraceLayer::new_for_http()
@@ -89,61 +165,140 @@ async fn health_check() -> impl IntoResponse {
 }
 
 /// Get all events and todos for today
-async fn get_today(State(state): State<AppState>) -> Result<Json<CombinedResponse>, ApiError> {
-    let (start, end) = get_today_range();
+async fn get_today(
+    State(state): State<AppState>,
+    Query(filter): Query<EventFilter>,
+) -> Result<Json<CombinedResponse>, ApiError> {
+    let (start, end) = in_local_zone(get_today_range());
     let data = state.data.read().await;
 
-    let events: Vec<_> = data
-        .events_in_range(start, end)
-        .into_iter()
-        .cloned()
-        .collect();
+    let mut events = data.events_in_range_with(start, end, &state.recurrence);
+    filter.apply(&mut events.items);
 
-    let todos: Vec<_> = data
-        .todos_in_range(start, end)
-        .into_iter()
-        .cloned()
-        .collect();
+    let todos = data.todos_in_range_with(start, end, &state.recurrence);
 
     Ok(Json(CombinedResponse {
-        events,
-        todos,
+        truncated_series: [events.truncated_series, todos.truncated_series].concat(),
+        events: events.items,
+        todos: todos.items,
         last_sync: data.last_sync,
+        outside_sync_window: state.outside_sync_window(start, end),
     }))
 }
 
 /// Get only calendar events for today
 async fn get_today_calendars(
     State(state): State<AppState>,
+    Query(filter): Query<EventFilter>,
 ) -> Result<Json<CalendarsResponse>, ApiError> {
-    let (start, end) = get_today_range();
+    let (start, end) = in_local_zone(get_today_range());
     let data = state.data.read().await;
 
-    let events: Vec<_> = data
-        .events_in_range(start, end)
-        .into_iter()
-        .cloned()
-        .collect();
+    let mut events = data.events_in_range_with(start, end, &state.recurrence);
+    filter.apply(&mut events.items);
 
     Ok(Json(CalendarsResponse {
-        events,
+        events: events.items,
         last_sync: data.last_sync,
+        truncated_series: events.truncated_series,
+        outside_sync_window: state.outside_sync_window(start, end),
     }))
 }
 
 /// Get only todos for today
 async fn get_today_todos(State(state): State<AppState>) -> Result<Json<TodosResponse>, ApiError> {
-    let (start, end) = get_today_range();
+    let (start, end) = in_local_zone(get_today_range());
     let data = state.data.read().await;
 
-    let todos: Vec<_> = data
-        .todos_in_range(start, end)
+    let todos = data.todos_in_range_with(start, end, &state.recurrence);
+
+    Ok(Json(TodosResponse {
+        todos: todos.items,
+        last_sync: data.last_sync,
+        truncated_series: todos.truncated_series,
+        outside_sync_window: state.outside_sync_window(start, end),
+    }))
+}
+
+/// API response for todos arranged by their subtask links
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TodoTreeResponse {
+    pub todos: Vec<TodoNode>,
+    pub last_sync: DateTime<Utc>,
+}
+
+/// Query parameters for the todo tree
+#[derive(Debug, Default, Deserialize)]
+struct TodoTreeParams {
+    /// Replace the progress of parents with the average of their subtasks
+    #[serde(default)]
+    derive_progress: bool,
+}
+
+/// Get all todos as a tree of subtasks (RELATED-TO)
+///
+/// Parent progress is derived from subtasks with `?derive_progress=true`.
+async fn get_todo_tree(
+    State(state): State<AppState>,
+    Query(params): Query<TodoTreeParams>,
+) -> Json<TodoTreeResponse> {
+    let data = state.data.read().await;
+    let todos = data
+        .current_todos()
         .into_iter()
-        .cloned()
+        .map(|todo| todo.in_zone(&Local))
         .collect();
 
-    Ok(Json(TodosResponse {
-        todos,
+    Json(TodoTreeResponse {
+        todos: build_todo_tree(todos, params.derive_progress),
+        last_sync: data.last_sync,
+    })
+}
+
+/// API response for journal entries
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalsResponse {
+    pub journals: Vec<Journal>,
+    pub last_sync: DateTime<Utc>,
+}
+
+/// Query parameters for journal entries
+#[derive(Debug, Default, Deserialize)]
+struct JournalParams {
+    /// Only entries dated in this range (same formats as `get_date_range`)
+    range: Option<String>,
+}
+
+/// Get journal entries (VJOURNAL), oldest first
+///
+/// All entries are returned unless `?range=` is given, in which case only
+/// entries dated in that range are.
+async fn get_journals(
+    State(state): State<AppState>,
+    Query(params): Query<JournalParams>,
+) -> Result<Json<JournalsResponse>, ApiError> {
+    let range = params
+        .range
+        .as_deref()
+        .map(parse_date_range)
+        .transpose()?
+        .map(in_local_zone);
+    let data = state.data.read().await;
+
+    let journals = if let Some((start, end)) = range {
+        data.journals_in_range(start, end)
+    } else {
+        let mut journals: Vec<Journal> = data
+            .journals
+            .iter()
+            .map(|journal| journal.clone().in_zone(&Local))
+            .collect();
+        journals.sort_by_key(|journal| journal.start);
+        journals
+    };
+
+    Ok(Json(JournalsResponse {
+        journals,
         last_sync: data.last_sync,
     }))
 }
@@ -159,29 +314,27 @@ async fn get_today_todos(State(state): State<AppState>) -> Result<Json<TodosResp
 /// - `"2026-01-05:2026-01-10"` - date range from:to
 /// - `"+3d"` - 3 days from now
 /// - `"-2d"` - 2 days ago
+///
+/// Declined events are left out with `?exclude_declined=true`.
 async fn get_date_range(
     State(state): State<AppState>,
     Path(range): Path<String>,
+    Query(filter): Query<EventFilter>,
 ) -> Result<Json<CombinedResponse>, ApiError> {
-    let (start, end) = parse_date_range(&range)?;
+    let (start, end) = in_local_zone(parse_date_range(&range)?);
     let data = state.data.read().await;
 
-    let events: Vec<_> = data
-        .events_in_range(start, end)
-        .into_iter()
-        .cloned()
-        .collect();
+    let mut events = data.events_in_range_with(start, end, &state.recurrence);
+    filter.apply(&mut events.items);
 
-    let todos: Vec<_> = data
-        .todos_in_range(start, end)
-        .into_iter()
-        .cloned()
-        .collect();
+    let todos = data.todos_in_range_with(start, end, &state.recurrence);
 
     Ok(Json(CombinedResponse {
-        events,
-        todos,
+        truncated_series: [events.truncated_series, todos.truncated_series].concat(),
+        events: events.items,
+        todos: todos.items,
         last_sync: data.last_sync,
+        outside_sync_window: state.outside_sync_window(start, end),
     }))
 }
 
@@ -235,6 +388,16 @@ async fn debug_events(State(state): State<AppState>) -> Result<Json<serde_json::
     })))
 }
 
+/// Express a query range in the local timezone
+///
+/// All-day events and date-only todos are placed on the server's local
+/// calendar dates rather than on UTC dates.
+fn in_local_zone(
+    (start, end): (DateTime<Utc>, DateTime<Utc>),
+) -> (DateTime<Local>, DateTime<Local>) {
+    (start.with_timezone(&Local), end.with_timezone(&Local))
+}
+
 /// Get the date range for "today" (midnight to midnight in local timezone, converted to UTC)
 ///
 /// This ensures that "today" is based on the user's local timezone, not UTC.
@@ -446,14 +609,17 @@ async fn trigger_sync(State(state): State<AppState>) -> Response {
             match sync_manager.sync().await {
                 Ok(()) => {
                     let data = state.data.read().await;
-                    Json(serde_json::json!({
+                    let body = serde_json::json!({
                         "status": "success",
                         "message": "Sync completed successfully",
                         "events": data.events.len(),
                         "todos": data.todos.len(),
-                        "last_sync": data.last_sync
-                    }))
-                    .into_response()
+                        "journals": data.journals.len(),
+                        "last_sync": data.last_sync,
+                        "sync_token_resets": sync_manager.sync_token_resets()
+                    });
+                    drop(data);
+                    Json(body).into_response()
                 }
                 Err(e) => {
                     error!("Manual sync failed: {:?}", e);
@@ -477,11 +643,138 @@ async fn trigger_sync(State(state): State<AppState>) -> Response {
     }
 }
 
+/// API response for active (delivered, unacknowledged) reminders
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemindersResponse {
+    pub reminders: Vec<DeliveredReminder>,
+}
+
+/// Query parameters for snoozing a reminder
+#[derive(Debug, Deserialize)]
+struct SnoozeParams {
+    minutes: Option<i64>,
+}
+
+/// Response used when no reminder scheduler is running
+fn reminders_unavailable() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ErrorResponse {
+            error: "Reminder scheduler not available".to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// Response for a reminder update (acknowledge or snooze)
+fn reminder_update_response(id: &str, result: anyhow::Result<bool>) -> Response {
+    match result {
+        Ok(true) => Json(serde_json::json!({ "status": "success", "id": id })).into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Reminder not found: {id}"),
+            }),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("Failed to update reminder {}: {:?}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to update reminder: {e}"),
+                }),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// List delivered reminders that have not been acknowledged
+async fn get_reminders(State(state): State<AppState>) -> Response {
+    match &state.reminders {
+        Some(reminders) => Json(RemindersResponse {
+            reminders: reminders.active().await,
+        })
+        .into_response(),
+        None => reminders_unavailable(),
+    }
+}
+
+/// Acknowledge a delivered reminder so it isn't delivered again
+async fn acknowledge_reminder(State(state): State<AppState>, Path(id): Path<String>) -> Response {
+    match &state.reminders {
+        Some(reminders) => reminder_update_response(&id, reminders.acknowledge(&id).await),
+        None => reminders_unavailable(),
+    }
+}
+
+/// Snooze a delivered reminder (`?minutes=N`, default 10 minutes)
+async fn snooze_reminder(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<SnoozeParams>,
+) -> Response {
+    let minutes = params.minutes.unwrap_or(DEFAULT_SNOOZE_MINUTES);
+    if minutes <= 0 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Snooze minutes must be positive".to_string(),
+            }),
+        )
+            .into_response();
+    }
+
+    let Some(until) =
+        Duration::try_minutes(minutes).and_then(|snooze| Utc::now().checked_add_signed(snooze))
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Snooze minutes out of range: {minutes}"),
+            }),
+        )
+            .into_response();
+    };
+
+    match &state.reminders {
+        Some(reminders) => reminder_update_response(&id, reminders.snooze(&id, until).await),
+        None => reminders_unavailable(),
+    }
+}
+
+/// Original iCalendar source of the resource containing the component `uid`
+async fn get_object_ics(State(state): State<AppState>, Path(uid): Path<String>) -> Response {
+    let data = state.data.read().await;
+    let Some(object) = data.object_for_uid(&uid) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Object not found: {uid}"),
+            }),
+        )
+            .into_response();
+    };
+
+    let mut response = (
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        object.ics.clone(),
+    )
+        .into_response();
+    if let Some(etag) = object.etag.as_deref().and_then(|etag| etag.parse().ok()) {
+        response.headers_mut().insert(header::ETAG, etag);
+    }
+    drop(data);
+
+    response
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
-    use crate::models::{CalendarEvent, Todo};
+    use crate::models::{CalendarEvent, Todo, TodoStatus};
     use axum::body::Body;
     use axum::http::{Request, StatusCode as HttpStatusCode};
     use chrono::{Datelike, TimeZone};
@@ -522,6 +815,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         });
 
         // Add a test todo for today
@@ -531,13 +849,29 @@ mod tests {
             description: None,
             due: Some(start_utc),
             start: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             completed: None,
             priority: None,
             percent_complete: None,
             calendar_name: "Test Calendar".to_string(),
             calendar_url: "/calendar/test".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         });
 
         data
@@ -545,7 +879,7 @@ mod tests {
 
     #[test]
     fn test_get_today_range() {
-        let (start, end) = get_today_range();
+        let (start, end) = in_local_zone(get_today_range());
         assert!(end > start);
         assert_eq!((end - start).num_days(), 1);
     }
@@ -691,7 +1025,7 @@ mod tests {
     #[tokio::test]
     async fn test_health_check_endpoint() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/health")
@@ -715,7 +1049,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_today_endpoint() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_today")
@@ -739,10 +1073,50 @@ mod tests {
         assert!(json.get("last_sync").is_some());
     }
 
+    #[tokio::test]
+    async fn test_outside_sync_window() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache = crate::cache::CacheManager::new_with_path(dir.path().to_path_buf()).unwrap();
+        let client = fast_dav_rs::CalDavClient::new("http://localhost", None, None).unwrap();
+        let sync_manager = SyncManager::new(client, cache)
+            .unwrap()
+            .with_sync_window(SyncWindow {
+                past_days: Some(30),
+                future_days: Some(30),
+            });
+        let data = Arc::new(RwLock::new(create_test_data()));
+        let app = create_router(
+            data,
+            Some(Arc::new(sync_manager)),
+            RecurrenceConfig::default(),
+            None,
+        );
+
+        let outside = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+                let response = ServiceExt::<Request<Body>>::oneshot(app, req)
+                    .await
+                    .unwrap();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let json: CombinedResponse = serde_json::from_slice(&body).unwrap();
+                json.outside_sync_window
+            }
+        };
+
+        assert!(!outside("/api/get_date_range/week").await);
+        assert!(outside("/api/get_date_range/+90d").await);
+        assert!(outside("/api/get_date_range/2000-01-01").await);
+    }
+
     #[tokio::test]
     async fn test_get_today_calendars_endpoint() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_today_calendars")
@@ -769,7 +1143,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_today_todos_endpoint() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_today_todos")
@@ -793,10 +1167,125 @@ mod tests {
         assert!(json.get("events").is_none()); // Should not have events
     }
 
+    #[tokio::test]
+    async fn test_get_todo_tree_endpoint() {
+        let mut data = create_test_data();
+        let parent = data.todos[0].clone();
+        for (uid, status) in [
+            ("sub-done", TodoStatus::Completed),
+            ("sub-open", TodoStatus::NeedsAction),
+        ] {
+            data.todos.push(Todo {
+                uid: uid.to_string(),
+                status,
+                parent_uid: Some(parent.uid.clone()),
+                ..parent.clone()
+            });
+        }
+        let app = create_router(
+            Arc::new(RwLock::new(data)),
+            None,
+            RecurrenceConfig::default(),
+            None,
+        );
+
+        let req = Request::builder()
+            .uri("/api/todos/tree?derive_progress=true")
+            .body(Body::empty())
+            .unwrap();
+        let response = ServiceExt::<Request<Body>>::oneshot(app, req)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), HttpStatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: TodoTreeResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json.todos.len(), 1);
+        let root = &json.todos[0];
+        assert_eq!(root.todo.uid, "test-todo-1");
+        assert_eq!(root.todo.percent_complete, Some(50));
+        let children: Vec<&str> = root.children.iter().map(|c| c.todo.uid.as_str()).collect();
+        assert_eq!(children, vec!["sub-done", "sub-open"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_journals_endpoint() {
+        let mut data = create_test_data();
+        let today = Local::now().date_naive();
+        for (uid, date) in [
+            ("later", today + Duration::days(3)),
+            ("today", today),
+            ("yesterday", today - Duration::days(1)),
+        ] {
+            data.journals.push(Journal {
+                uid: uid.to_string(),
+                summary: format!("Notes {uid}"),
+                description: None,
+                start: None,
+                status: None,
+                calendar_name: "Notes".to_string(),
+                calendar_url: "/calendar/notes".to_string(),
+                etag: None,
+                tzid: None,
+                start_date: Some(date),
+                categories: Vec::new(),
+                url: None,
+                class: None,
+                created: None,
+                last_modified: None,
+                href: None,
+            });
+        }
+        let app = create_router(
+            Arc::new(RwLock::new(data)),
+            None,
+            RecurrenceConfig::default(),
+            None,
+        );
+
+        let get = |uri: &str| {
+            let app = app.clone();
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            async move {
+                let response = ServiceExt::<Request<Body>>::oneshot(app, req)
+                    .await
+                    .unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (status, body)
+            }
+        };
+        let uids = |body: &[u8]| -> Vec<String> {
+            let json: JournalsResponse = serde_json::from_slice(body).unwrap();
+            json.journals.into_iter().map(|j| j.uid).collect()
+        };
+
+        // All entries, oldest first
+        let (status, body) = get("/api/journals").await;
+        assert_eq!(status, HttpStatusCode::OK);
+        assert_eq!(uids(&body), vec!["yesterday", "today", "later"]);
+
+        let (status, body) = get("/api/journals?range=today").await;
+        assert_eq!(status, HttpStatusCode::OK);
+        assert_eq!(uids(&body), vec!["today"]);
+
+        let (status, body) = get("/api/journals?range=week").await;
+        assert_eq!(status, HttpStatusCode::OK);
+        assert_eq!(uids(&body), vec!["today", "later"]);
+
+        let (status, _) = get("/api/journals?range=bogus").await;
+        assert_eq!(status, HttpStatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_get_date_range_endpoint_today() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/today")
@@ -823,7 +1312,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_week() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/week")
@@ -840,7 +1329,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_invalid() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/invalid-range")
@@ -865,7 +1354,7 @@ mod tests {
     #[tokio::test]
     async fn test_debug_events_endpoint() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/debug/events")
@@ -897,7 +1386,7 @@ mod tests {
     #[tokio::test]
     async fn test_trigger_sync_without_sync_manager() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .method("POST")
@@ -932,7 +1421,7 @@ mod tests {
     #[test]
     fn test_create_router() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let router = create_router(data, None);
+        let router = create_router(data, None, RecurrenceConfig::default(), None);
         // Just verify the router is created without panicking
         assert!(std::mem::size_of_val(&router) > 0);
     }
@@ -947,7 +1436,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_specific_date() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/2026-01-05")
@@ -964,7 +1453,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_date_range() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/2026-01-05:2026-01-10")
@@ -981,7 +1470,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_relative_positive() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/+3d")
@@ -998,7 +1487,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_relative_negative() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/-2d")
@@ -1015,7 +1504,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_tomorrow() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/tomorrow")
@@ -1032,7 +1521,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_date_range_endpoint_month() {
         let data = Arc::new(RwLock::new(create_test_data()));
-        let app = create_router(data, None);
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
 
         let req = Request::builder()
             .uri("/api/get_date_range/month")
@@ -1046,6 +1535,166 @@ mod tests {
         assert_eq!(response.status(), HttpStatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_reminders_endpoints_without_scheduler() {
+        let data = Arc::new(RwLock::new(create_test_data()));
+        let app = create_router(data, None, RecurrenceConfig::default(), None);
+
+        let req = Request::builder()
+            .uri("/api/reminders")
+            .body(Body::empty())
+            .unwrap();
+        let response = ServiceExt::<Request<Body>>::oneshot(app, req)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), HttpStatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_reminders_endpoints() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache = crate::cache::CacheManager::new_with_path(dir.path().to_path_buf()).unwrap();
+        let data = Arc::new(RwLock::new(create_test_data()));
+        let scheduler =
+            Arc::new(ReminderScheduler::new(Arc::clone(&data), cache, Vec::new()).unwrap());
+        let app = create_router(data, None, RecurrenceConfig::default(), Some(scheduler));
+
+        let request = |method: &str, uri: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response =
+            ServiceExt::<Request<Body>>::oneshot(app.clone(), request("GET", "/api/reminders"))
+                .await
+                .unwrap();
+        assert_eq!(response.status(), HttpStatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: RemindersResponse = serde_json::from_slice(&body).unwrap();
+        assert!(json.reminders.is_empty());
+
+        // Unknown reminders can't be acknowledged, and snoozes must be positive
+        let response = ServiceExt::<Request<Body>>::oneshot(
+            app.clone(),
+            request("POST", "/api/reminders/unknown/acknowledge"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), HttpStatusCode::NOT_FOUND);
+
+        let response = ServiceExt::<Request<Body>>::oneshot(
+            app.clone(),
+            request("POST", "/api/reminders/unknown/snooze?minutes=0"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), HttpStatusCode::BAD_REQUEST);
+
+        // Snoozes too far in the future are rejected rather than overflowing
+        let response = ServiceExt::<Request<Body>>::oneshot(
+            app,
+            request(
+                "POST",
+                &format!("/api/reminders/unknown/snooze?minutes={}", i64::MAX),
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), HttpStatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_exclude_declined_events() {
+        let mut data = create_test_data();
+        let (start, end) = in_local_zone(get_today_range());
+        let today_events = data.events_in_range(start, end);
+        data.events
+            .iter_mut()
+            .for_each(|event| event.own_partstat = Some("DECLINED".to_string()));
+        let app = create_router(
+            Arc::new(RwLock::new(data)),
+            None,
+            RecurrenceConfig::default(),
+            None,
+        );
+
+        let count_events = |app: Router, uri: &'static str| async move {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let response = ServiceExt::<Request<Body>>::oneshot(app, req)
+                .await
+                .unwrap();
+            assert_eq!(response.status(), HttpStatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: CalendarsResponse = serde_json::from_slice(&body).unwrap();
+            json.events.len()
+        };
+
+        assert!(!today_events.is_empty());
+        assert_eq!(
+            count_events(app.clone(), "/api/get_today_calendars").await,
+            today_events.len()
+        );
+        assert_eq!(
+            count_events(app, "/api/get_today_calendars?exclude_declined=true").await,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_object_ics_endpoint() {
+        let mut data = create_test_data();
+        let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:raw@example.com\r\nX-CUSTOM:kept\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        data.objects.insert(
+            "/cal/raw.ics".to_string(),
+            crate::models::CalendarObject {
+                href: "/cal/raw.ics".to_string(),
+                calendar_url: "/cal/".to_string(),
+                etag: Some("\"etag-1\"".to_string()),
+                uids: vec!["raw@example.com".to_string()],
+                ics: ics.to_string(),
+            },
+        );
+        let app = create_router(
+            Arc::new(RwLock::new(data)),
+            None,
+            RecurrenceConfig::default(),
+            None,
+        );
+
+        let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = ServiceExt::<Request<Body>>::oneshot(
+            app.clone(),
+            request("/api/objects/raw@example.com/ics"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), HttpStatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/calendar; charset=utf-8"
+        );
+        assert_eq!(response.headers()[header::ETAG], "\"etag-1\"");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, ics.as_bytes());
+
+        let response =
+            ServiceExt::<Request<Body>>::oneshot(app, request("/api/objects/missing/ics"))
+                .await
+                .unwrap();
+        assert_eq!(response.status(), HttpStatusCode::NOT_FOUND);
+    }
+
     // Note: Full endpoint testing is done in integration tests
     // These basic tests verify the router configuration
 }
@@ -3,7 +3,8 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
-use crate::models::CalendarData;
+use crate::models::{CACHE_VERSION, CalendarData};
+use crate::reminders::ReminderState;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::PathBuf;
@@ -61,9 +62,16 @@ impl CacheManager {
         self.cache_dir.join("calendar_data.json")
     }
 
+    /// Get the path to the reminder delivery state file
+    fn reminders_file_path(&self) -> PathBuf {
+        self.cache_dir.join("reminders.json")
+    }
+
     /// Load calendar data from cache
     ///
-    /// Returns Ok(None) if cache doesn't exist or is invalid
+    /// Returns Ok(None) if cache doesn't exist or is invalid. A cache written
+    /// by an older version loses its items and sync state, so every calendar
+    /// is fetched again on the next sync.
     ///
     /// # Errors
     ///
@@ -79,9 +87,26 @@ impl CacheManager {
         debug!("Loading cache from: {:?}", cache_path);
         let contents = fs::read_to_string(&cache_path).context("Failed to read cache file")?;
 
-        let data: CalendarData =
+        let mut data: CalendarData =
             serde_json::from_str(&contents).context("Failed to parse cache file")?;
 
+        // Older caches hold items stored differently (e.g. recurring series
+        // as pre-expanded instances), and their sync tokens would keep
+        // unchanged resources from being fetched again
+        if data.version < CACHE_VERSION {
+            info!(
+                "Cache format version {} is outdated (current {}), resyncing all calendars",
+                data.version, CACHE_VERSION
+            );
+            data.events.clear();
+            data.todos.clear();
+            data.journals.clear();
+            data.objects.clear();
+            data.sync_tokens.clear();
+            data.ctags.clear();
+            data.version = CACHE_VERSION;
+        }
+
         Ok(Some(data))
     }
 
@@ -109,6 +134,44 @@ impl CacheManager {
         Ok(())
     }
 
+    /// Load reminder delivery state from cache
+    ///
+    /// Returns Ok(None) if no reminders have been delivered yet
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be read or parsed.
+    pub fn load_reminders(&self) -> Result<Option<ReminderState>> {
+        let path = self.reminders_file_path();
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(&path).context("Failed to read reminder state")?;
+        let state: ReminderState =
+            serde_json::from_str(&contents).context("Failed to parse reminder state")?;
+
+        Ok(Some(state))
+    }
+
+    /// Save reminder delivery state to cache
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be written or serialization fails.
+    pub fn save_reminders(&self, state: &ReminderState) -> Result<()> {
+        let path = self.reminders_file_path();
+
+        debug!("Saving reminder state to: {:?}", path);
+        let json =
+            serde_json::to_string_pretty(state).context("Failed to serialize reminder state")?;
+
+        fs::write(&path, json).context("Failed to write reminder state")?;
+
+        Ok(())
+    }
+
     /// Clear the cache
     ///
     /// # Errors
@@ -156,7 +219,7 @@ impl Default for CacheManager {
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
-    use crate::models::{CalendarEvent, Todo};
+    use crate::models::{CalendarEvent, EventStatus, Todo, TodoStatus};
     use chrono::{TimeZone, Utc};
     use tempfile::TempDir;
     use tempfile::tempdir;
@@ -198,6 +261,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         });
 
         cache.save(&data)?;
@@ -213,6 +301,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_load_outdated_cache_resyncs() -> Result<()> {
+        let (cache, _temp_dir) = create_test_cache_manager()?;
+
+        let mut data = CalendarData::new();
+        data.sync_tokens
+            .insert("/cal/".to_string(), "token-1".to_string());
+        data.ctags.insert("/cal/".to_string(), "ctag-1".to_string());
+        cache.save(&data)?;
+        assert_eq!(cache.load()?.unwrap().sync_tokens.len(), 1);
+
+        // Caches written before the version was recorded have no version
+        let mut json = serde_json::to_value(&data)?;
+        json.as_object_mut().unwrap().remove("version");
+        fs::write(cache.cache_file_path(), json.to_string())?;
+
+        let loaded = cache.load()?.unwrap();
+        assert!(loaded.sync_tokens.is_empty());
+        assert!(loaded.ctags.is_empty());
+        assert_eq!(loaded.version, CACHE_VERSION);
+
+        Ok(())
+    }
+
     #[test]
     fn test_load_nonexistent_cache() -> Result<()> {
         let (cache, _temp_dir) = create_test_cache_manager()?;
@@ -265,10 +377,26 @@ mod tests {
             completed: None,
             priority: Some(1),
             percent_complete: Some(50),
-            status: "IN-PROCESS".to_string(),
+            status: TodoStatus::InProcess,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         });
 
         cache.save(&data)?;
@@ -353,8 +481,33 @@ mod tests {
             all_day: false,
             rrule: Some("FREQ=DAILY".to_string()),
             exdates: Vec::new(),
-            status: Some("CONFIRMED".to_string()),
+            status: Some(EventStatus::Confirmed),
             etag: Some("event-etag".to_string()),
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         });
         cache.save(&data1)?;
 
@@ -373,10 +526,26 @@ mod tests {
             completed: None,
             priority: Some(5),
             percent_complete: Some(25),
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: Some("etag2".to_string()),
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         });
         cache.save(&data2)?;
 
@@ -424,6 +593,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         });
 
         // Add todo
@@ -444,10 +638,26 @@ mod tests {
             ),
             priority: Some(3),
             percent_complete: Some(100),
-            status: "COMPLETED".to_string(),
+            status: TodoStatus::Completed,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         });
 
         cache.save(&data)?;
@@ -458,7 +668,7 @@ mod tests {
         assert_eq!(loaded.events[0].location, Some("Office".to_string()));
         assert_eq!(loaded.events[0].rrule, Some("FREQ=DAILY".to_string()));
         assert_eq!(loaded.todos[0].percent_complete, Some(100));
-        assert_eq!(loaded.todos[0].status, "COMPLETED");
+        assert_eq!(loaded.todos[0].status, TodoStatus::Completed);
 
         Ok(())
     }
@@ -539,8 +749,33 @@ mod tests {
             all_day: false,
             rrule: Some("FREQ=WEEKLY;BYDAY=MO".to_string()),
             exdates: Vec::new(),
-            status: Some("CONFIRMED".to_string()),
+            status: Some(EventStatus::Confirmed),
             etag: Some("full-etag-123".to_string()),
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         });
 
         cache.save(&data)?;
@@ -552,7 +787,7 @@ mod tests {
         assert_eq!(event.description, Some("Full description".to_string()));
         assert_eq!(event.location, Some("Conference Room A".to_string()));
         assert_eq!(event.calendar_color, Some("#0000FF".to_string()));
-        assert_eq!(event.status, Some("CONFIRMED".to_string()));
+        assert_eq!(event.status, Some(EventStatus::Confirmed));
         assert_eq!(event.etag, Some("full-etag-123".to_string()));
         assert_eq!(event.rrule, Some("FREQ=WEEKLY;BYDAY=MO".to_string()));
 
@@ -581,10 +816,26 @@ mod tests {
             ),
             priority: Some(1),
             percent_complete: Some(100),
-            status: "COMPLETED".to_string(),
+            status: TodoStatus::Completed,
             calendar_name: "Personal Tasks".to_string(),
             calendar_url: "/calendars/personal-tasks".to_string(),
             etag: Some("todo-etag-67890".to_string()),
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         });
 
         cache.save(&data)?;
@@ -640,6 +891,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         });
         cache.save(&data1)?;
         assert!(cache.exists());
@@ -659,10 +935,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         });
         cache.save(&data2)?;
 
@@ -675,6 +967,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_save_and_load_reminders() -> Result<()> {
+        let (cache, _temp_dir) = create_test_cache_manager()?;
+
+        // No state until reminders have been delivered
+        assert!(cache.load_reminders()?.is_none());
+
+        let state = ReminderState::default();
+        cache.save_reminders(&state)?;
+        assert_eq!(cache.load_reminders()?, Some(state));
+
+        // Reminder state lives next to, not inside, the calendar cache
+        assert!(!cache.exists());
+
+        Ok(())
+    }
+
     // Note: The following scenarios are not covered by tests because they require
     // special environmental conditions or mocking that's difficult to set up:
     //
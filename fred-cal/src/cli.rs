@@ -3,6 +3,9 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
+use crate::recurrence::{DEFAULT_MAX_INSTANCES, RecurrenceConfig};
+use crate::reminders::ReminderSink;
+use crate::sync::SyncWindow;
 use anyhow::{Context, Result};
 use clap::Parser;
 use std::path::PathBuf;
@@ -26,6 +29,32 @@ pub struct Cli {
     /// Port for the API server to listen on
     #[arg(long, env = "API_PORT", default_value = "3000")]
     pub port: u16,
+
+    /// Only expand recurring series this many days into the past (unbounded if unset)
+    #[arg(long, env = "RECURRENCE_PAST_DAYS")]
+    pub recurrence_past_days: Option<u32>,
+
+    /// Only expand recurring series this many days into the future (unbounded if unset)
+    #[arg(long, env = "RECURRENCE_FUTURE_DAYS")]
+    pub recurrence_future_days: Option<u32>,
+
+    /// Maximum occurrences generated per recurring series and request (0 = unlimited)
+    #[arg(long, env = "MAX_INSTANCES_PER_SERIES", default_value_t = DEFAULT_MAX_INSTANCES)]
+    pub max_instances_per_series: usize,
+
+    /// Only sync events and todos this many days into the past (unbounded if unset)
+    #[arg(long, env = "SYNC_PAST_DAYS")]
+    pub sync_past_days: Option<u32>,
+
+    /// Only sync events and todos this many days into the future (unbounded if unset)
+    #[arg(long, env = "SYNC_FUTURE_DAYS")]
+    pub sync_future_days: Option<u32>,
+
+    /// Where to deliver alarm reminders: webhook:<url>, command:<cmd> or pipe:<path>
+    /// (repeatable; one per line in the environment variable, as URLs and
+    /// commands may contain commas)
+    #[arg(long = "reminder-sink", env = "REMINDER_SINKS", value_delimiter = '\n')]
+    pub reminder_sinks: Vec<ReminderSink>,
 }
 
 /// Credentials for `CalDAV` authentication
@@ -43,6 +72,29 @@ impl Cli {
         Self::parse()
     }
 
+    /// Build the recurrence expansion limits from CLI arguments
+    #[must_use]
+    pub const fn recurrence_config(&self) -> RecurrenceConfig {
+        RecurrenceConfig {
+            past_days: self.recurrence_past_days,
+            future_days: self.recurrence_future_days,
+            max_instances: if self.max_instances_per_series == 0 {
+                None
+            } else {
+                Some(self.max_instances_per_series)
+            },
+        }
+    }
+
+    /// Build the sync window from CLI arguments
+    #[must_use]
+    pub const fn sync_window(&self) -> SyncWindow {
+        SyncWindow {
+            past_days: self.sync_past_days,
+            future_days: self.sync_future_days,
+        }
+    }
+
     /// Load and validate credentials from CLI arguments
     ///
     /// Each argument can be either a direct value or a path to a file.
@@ -229,6 +281,12 @@ mod tests {
             username: "testuser".to_string(),
             password: "testpass".to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let creds = cli.load_credentials()?;
@@ -255,6 +313,12 @@ mod tests {
             username: user_file.path().to_str().expect("path").to_string(),
             password: pass_file.path().to_str().expect("path").to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let creds = cli.load_credentials()?;
@@ -275,6 +339,12 @@ mod tests {
             username: user_file.path().to_str().expect("path").to_string(),
             password: "directpass".to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let creds = cli.load_credentials()?;
@@ -292,6 +362,12 @@ mod tests {
             username: "user".to_string(),
             password: "pass".to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let result = cli.load_credentials();
@@ -305,6 +381,12 @@ mod tests {
             username: String::new(),
             password: "pass".to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let result = cli.load_credentials();
@@ -318,6 +400,12 @@ mod tests {
             username: "user".to_string(),
             password: String::new(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let result = cli.load_credentials();
@@ -331,6 +419,12 @@ mod tests {
             username: "user".to_string(),
             password: "pass".to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let result = cli.load_credentials();
@@ -344,6 +438,12 @@ mod tests {
             username: "user".to_string(),
             password: "pass".to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let creds = cli.load_credentials()?;
@@ -368,6 +468,12 @@ mod tests {
             username: user_file.path().to_str().expect("path").to_string(),
             password: pass_file.path().to_str().expect("path").to_string(),
             port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let creds = cli.load_credentials()?;
@@ -399,6 +505,12 @@ mod tests {
             username: "user".to_string(),
             password: "pass".to_string(),
             port: 8080,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         let debug_str = format!("{cli:?}");
@@ -449,11 +561,74 @@ mod tests {
             username: "user".to_string(),
             password: "pass".to_string(),
             port: 9999,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
         };
 
         assert_eq!(cli.port, 9999);
     }
 
+    #[test]
+    fn test_recurrence_config() {
+        let mut cli = Cli {
+            caldav_server: "https://example.com".to_string(),
+            username: "user".to_string(),
+            password: "pass".to_string(),
+            port: 3000,
+            recurrence_past_days: Some(30),
+            recurrence_future_days: Some(365),
+            max_instances_per_series: 50,
+            sync_past_days: None,
+            sync_future_days: None,
+            reminder_sinks: Vec::new(),
+        };
+
+        assert_eq!(
+            cli.recurrence_config(),
+            RecurrenceConfig {
+                past_days: Some(30),
+                future_days: Some(365),
+                max_instances: Some(50),
+            }
+        );
+
+        // Zero disables the per-series cap
+        cli.max_instances_per_series = 0;
+        assert_eq!(cli.recurrence_config().max_instances, None);
+    }
+
+    #[test]
+    fn test_sync_window() {
+        let mut cli = Cli {
+            caldav_server: "https://example.com".to_string(),
+            username: "user".to_string(),
+            password: "pass".to_string(),
+            port: 3000,
+            recurrence_past_days: None,
+            recurrence_future_days: None,
+            max_instances_per_series: DEFAULT_MAX_INSTANCES,
+            sync_past_days: Some(365),
+            sync_future_days: Some(730),
+            reminder_sinks: Vec::new(),
+        };
+
+        assert_eq!(
+            cli.sync_window(),
+            SyncWindow {
+                past_days: Some(365),
+                future_days: Some(730),
+            }
+        );
+
+        cli.sync_past_days = None;
+        cli.sync_future_days = None;
+        assert!(!cli.sync_window().is_bounded());
+    }
+
     #[test]
     fn test_validate_credentials_various_valid_urls() {
         // Test various valid URL formats
@@ -507,6 +682,58 @@ mod tests {
         assert_eq!(cli.port, 9090);
     }
 
+    #[test]
+    fn test_parse_reminder_sinks_with_commas() {
+        use temp_env;
+
+        temp_env::with_var_unset("REMINDER_SINKS", || {
+            let cli = Cli::parse_from([
+                "test_program",
+                "--caldav-server",
+                "https://example.com",
+                "--username",
+                "user",
+                "--password",
+                "pass",
+                "--reminder-sink",
+                "webhook:https://example.com/hook?a=1,b=2",
+                "--reminder-sink",
+                r#"command:notify-send "a, b""#,
+            ]);
+
+            assert_eq!(
+                cli.reminder_sinks,
+                vec![
+                    ReminderSink::Webhook("https://example.com/hook?a=1,b=2".to_string()),
+                    ReminderSink::Command(r#"notify-send "a, b""#.to_string()),
+                ]
+            );
+        });
+
+        temp_env::with_var(
+            "REMINDER_SINKS",
+            Some("webhook:https://example.com/hook?a=1,b=2\ncommand:logger a, b"),
+            || {
+                let cli = Cli::parse_from([
+                    "test_program",
+                    "--caldav-server",
+                    "https://example.com",
+                    "--username",
+                    "user",
+                    "--password",
+                    "pass",
+                ]);
+                assert_eq!(
+                    cli.reminder_sinks,
+                    vec![
+                        ReminderSink::Webhook("https://example.com/hook?a=1,b=2".to_string()),
+                        ReminderSink::Command("logger a, b".to_string()),
+                    ]
+                );
+            },
+        );
+    }
+
     #[test]
     fn test_parse_args_default_port() {
         use temp_env;
@@ -22,4 +22,7 @@ pub mod cache;
 pub mod cli;
 pub mod models;
 pub mod recurrence;
+pub mod reminders;
+pub mod subtasks;
 pub mod sync;
+pub mod timezone;
@@ -8,6 +8,7 @@ use fast_dav_rs::CalDavClient;
 use fred_cal::api::create_router;
 use fred_cal::cache::CacheManager;
 use fred_cal::cli::Cli;
+use fred_cal::reminders::ReminderScheduler;
 use fred_cal::sync::SyncManager;
 use std::sync::Arc;
 use tracing_subscriber::{EnvFilter, fmt};
@@ -38,6 +39,9 @@ async fn main() -> Result<()> {
     // Parse command line arguments
     let cli = Cli::parse_args();
     let port = cli.port;
+    let recurrence = cli.recurrence_config();
+    let sync_window = cli.sync_window();
+    let reminder_sinks = cli.reminder_sinks.clone();
 
     // Load and validate credentials
     let credentials = cli.load_credentials()?;
@@ -48,6 +52,7 @@ async fn main() -> Result<()> {
     // Initialize cache manager
     let cache = CacheManager::new()?;
     info!("Cache directory: {:?}", cache.cache_directory());
+    let reminder_cache = CacheManager::new_with_path(cache.cache_directory().clone())?;
 
     // Create CalDAV client
     let client = CalDavClient::new(
@@ -57,7 +62,13 @@ async fn main() -> Result<()> {
     )?;
 
     // Create sync manager
-    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?.with_sync_window(sync_window));
+    if sync_window.is_bounded() {
+        info!(
+            "Sync window: {:?} days back, {:?} days forward",
+            sync_window.past_days, sync_window.future_days
+        );
+    }
 
     // Perform initial sync
     info!("Performing initial sync...");
@@ -75,8 +86,26 @@ async fn main() -> Result<()> {
         })
     };
 
+    // Start reminder scheduler (checks for due alarms every 30 seconds)
+    let reminders = Arc::new(ReminderScheduler::new(
+        Arc::clone(&calendar_data),
+        reminder_cache,
+        reminder_sinks,
+    )?);
+    {
+        let reminders = Arc::clone(&reminders);
+        tokio::spawn(async move {
+            reminders.start(30).await;
+        });
+    }
+
     // Create and start web server
-    let app = create_router(calendar_data, Some(Arc::clone(&sync_manager)));
+    let app = create_router(
+        calendar_data,
+        Some(Arc::clone(&sync_manager)),
+        recurrence,
+        Some(reminders),
+    );
     let bind_addr = format!("0.0.0.0:{port}");
     let listener = tokio::net::TcpListener::bind(&bind_addr).await?;
 
@@ -86,9 +115,15 @@ async fn main() -> Result<()> {
     info!("  - GET /api/get_today");
     info!("  - GET /api/get_today_calendars");
     info!("  - GET /api/get_today_todos");
+    info!("  - GET /api/todos/tree");
+    info!("  - GET /api/journals");
     info!("  - GET /api/get_date_range/:range");
     info!("  - GET /api/debug/events (diagnostic endpoint)");
     info!("  - POST /api/sync (trigger manual sync)");
+    info!("  - GET /api/reminders");
+    info!("  - POST /api/reminders/:id/acknowledge");
+    info!("  - POST /api/reminders/:id/snooze");
+    info!("  - GET /api/objects/:uid/ics");
 
     // Run the server
     axum::serve(listener, app).await?;
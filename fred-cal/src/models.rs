@@ -3,11 +3,19 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
-use chrono::{DateTime, Utc};
+use crate::recurrence::{
+    RecurrenceConfig, expand_recurring_event_limited, expand_recurring_todo_limited,
+    next_pending_todo, overlaps,
+};
+use crate::timezone::VTimezone;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 /// Represents a calendar event
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CalendarEvent {
     /// Unique identifier for the event
     pub uid: String,
@@ -39,17 +47,186 @@ pub struct CalendarEvent {
     /// Whether this is an all-day event
     pub all_day: bool,
 
+    /// Start date of an all-day event (floating, not tied to any timezone)
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+
+    /// End date (exclusive) of an all-day event
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+
+    /// Original TZID of the start time (if any)
+    #[serde(default)]
+    pub tzid: Option<String>,
+
+    /// Whether the start time is floating (local time without a timezone)
+    #[serde(default)]
+    pub floating: bool,
+
+    /// Definition of `tzid` when only the calendar's VTIMEZONE defines it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vtimezone: Option<VTimezone>,
+
     /// Recurrence rule (if any)
     pub rrule: Option<String>,
 
     /// Exception dates (EXDATE) for recurring events - dates to exclude
     pub exdates: Vec<DateTime<Utc>>,
 
+    /// Date-only exception dates (EXDATE;VALUE=DATE) - days to exclude
+    #[serde(default)]
+    pub exdate_dates: Vec<NaiveDate>,
+
+    /// Additional recurrence dates (RDATE) for recurring events
+    #[serde(default)]
+    pub rdates: Vec<DateTime<Utc>>,
+
     /// Event status (CONFIRMED, TENTATIVE, CANCELLED)
-    pub status: Option<String>,
+    pub status: Option<EventStatus>,
 
     /// `ETag` for sync purposes
     pub etag: Option<String>,
+
+    /// Original start of this occurrence within its series (RECURRENCE-ID)
+    #[serde(default)]
+    pub recurrence_id: Option<DateTime<Utc>>,
+
+    /// Stable identifier of this occurrence (UID plus original start for series)
+    #[serde(default)]
+    pub instance_id: String,
+
+    /// UID of the recurring series this event belongs to (if any)
+    #[serde(default)]
+    pub series_uid: Option<String>,
+
+    /// Reminders (VALARM) with triggers resolved for this occurrence
+    #[serde(default)]
+    pub alarms: Vec<Alarm>,
+
+    /// Organizer of the event (ORGANIZER)
+    #[serde(default)]
+    pub organizer: Option<Organizer>,
+
+    /// Invited participants (ATTENDEE)
+    #[serde(default)]
+    pub attendees: Vec<Attendee>,
+
+    /// Participation status of the current user, if they are an attendee
+    #[serde(default)]
+    pub own_partstat: Option<String>,
+
+    /// Categories (CATEGORIES)
+    #[serde(default)]
+    pub categories: Vec<String>,
+
+    /// Link to a page about the event (URL)
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Access classification (PUBLIC, PRIVATE, CONFIDENTIAL)
+    #[serde(default)]
+    pub class: Option<String>,
+
+    /// Whether the event blocks time (OPAQUE) or not (TRANSPARENT)
+    #[serde(default)]
+    pub transparency: Option<String>,
+
+    /// Revision number (SEQUENCE)
+    #[serde(default)]
+    pub sequence: Option<u32>,
+
+    /// When the event was created (CREATED)
+    #[serde(default)]
+    pub created: Option<DateTime<Utc>>,
+
+    /// When the event was last modified (LAST-MODIFIED)
+    #[serde(default)]
+    pub last_modified: Option<DateTime<Utc>>,
+
+    /// Geographic position (GEO)
+    #[serde(default)]
+    pub geo: Option<Geo>,
+
+    /// Priority (1-9, 1 being highest)
+    #[serde(default)]
+    pub priority: Option<u8>,
+
+    /// Online meetings (CONFERENCE, X-GOOGLE-CONFERENCE or meeting links in the text)
+    #[serde(default)]
+    pub conferences: Vec<Conference>,
+
+    /// Href of the calendar object resource the event was fetched from
+    #[serde(default)]
+    pub href: Option<String>,
+}
+
+impl CalendarEvent {
+    /// Build the instance identifier for an occurrence of `uid`
+    ///
+    /// Occurrences of a series are identified by the UID plus their original
+    /// start in UTC (e.g. `standup@example.com:20260105T100000Z`); single
+    /// events use their UID as is.
+    #[must_use]
+    pub fn make_instance_id(uid: &str, recurrence_id: Option<DateTime<Utc>>) -> String {
+        recurrence_id.map_or_else(
+            || uid.to_string(),
+            |rid| format!("{uid}:{}", rid.format("%Y%m%dT%H%M%SZ")),
+        )
+    }
+
+    /// Check whether the event has been cancelled (STATUS:CANCELLED)
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.status == Some(EventStatus::Cancelled)
+    }
+
+    /// Check whether the current user declined the event (PARTSTAT=DECLINED)
+    #[must_use]
+    pub fn is_declined(&self) -> bool {
+        self.own_partstat
+            .as_deref()
+            .is_some_and(|partstat| partstat.eq_ignore_ascii_case("declined"))
+    }
+
+    /// Participation status of the attendee matching one of `addresses`
+    ///
+    /// Addresses are the user's calendar-user-address-set and are compared
+    /// case-insensitively.
+    #[must_use]
+    pub fn partstat_for(&self, addresses: &[String]) -> Option<String> {
+        self.attendees
+            .iter()
+            .find(|attendee| {
+                addresses
+                    .iter()
+                    .any(|address| attendee.address.eq_ignore_ascii_case(address))
+            })
+            .map(|attendee| attendee.partstat.clone())
+    }
+
+    /// Pin an all-day event to midnight of its dates in `zone`
+    ///
+    /// All-day dates are floating, so they start at a different instant for
+    /// every observer. Timed events are returned unchanged.
+    #[must_use]
+    pub fn in_zone<Z: TimeZone>(mut self, zone: &Z) -> Self {
+        if let (Some(start), Some(end)) = (
+            self.start_date.and_then(|date| midnight_in(date, zone)),
+            self.end_date.and_then(|date| midnight_in(date, zone)),
+        ) {
+            self.start = start;
+            self.end = end;
+            self.resolve_alarms();
+        }
+        self
+    }
+
+    /// Recompute relative alarm triggers from the current start and end
+    pub fn resolve_alarms(&mut self) {
+        for alarm in &mut self.alarms {
+            alarm.resolve(Some(self.start), Some(self.end));
+        }
+    }
 }
 
 /// Represents a todo/task
@@ -80,7 +257,7 @@ pub struct Todo {
     pub percent_complete: Option<u8>,
 
     /// Status (NEEDS-ACTION, IN-PROCESS, COMPLETED, CANCELLED)
-    pub status: String,
+    pub status: TodoStatus,
 
     /// Calendar this todo belongs to
     pub calendar_name: String,
@@ -90,6 +267,436 @@ pub struct Todo {
 
     /// `ETag` for sync purposes
     pub etag: Option<String>,
+
+    /// Recurrence rule (if any)
+    #[serde(default)]
+    pub rrule: Option<String>,
+
+    /// Original due date of this occurrence within its series (RECURRENCE-ID)
+    #[serde(default)]
+    pub recurrence_id: Option<DateTime<Utc>>,
+
+    /// Original TZID of the due (or start) time (if any)
+    #[serde(default)]
+    pub tzid: Option<String>,
+
+    /// Whether the due (or start) time is floating (local time without a timezone)
+    #[serde(default)]
+    pub floating: bool,
+
+    /// Due date when DUE is a date without a time (floating)
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,
+
+    /// Start date when DTSTART is a date without a time (floating)
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+
+    /// Reminders (VALARM) with triggers resolved for this occurrence
+    #[serde(default)]
+    pub alarms: Vec<Alarm>,
+
+    /// Categories (CATEGORIES)
+    #[serde(default)]
+    pub categories: Vec<String>,
+
+    /// Link to a page about the todo (URL)
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Access classification (PUBLIC, PRIVATE, CONFIDENTIAL)
+    #[serde(default)]
+    pub class: Option<String>,
+
+    /// Revision number (SEQUENCE)
+    #[serde(default)]
+    pub sequence: Option<u32>,
+
+    /// When the todo was created (CREATED)
+    #[serde(default)]
+    pub created: Option<DateTime<Utc>>,
+
+    /// When the todo was last modified (LAST-MODIFIED)
+    #[serde(default)]
+    pub last_modified: Option<DateTime<Utc>>,
+
+    /// UID of the parent todo (RELATED-TO with RELTYPE=PARENT, the default)
+    #[serde(default)]
+    pub parent_uid: Option<String>,
+
+    /// UIDs of child todos listed on this todo (RELATED-TO;RELTYPE=CHILD)
+    #[serde(default)]
+    pub child_uids: Vec<String>,
+
+    /// Href of the calendar object resource the todo was fetched from
+    #[serde(default)]
+    pub href: Option<String>,
+}
+
+impl Todo {
+    /// Check whether the todo has been completed (STATUS:COMPLETED)
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.status == TodoStatus::Completed
+    }
+
+    /// Check whether the todo has been cancelled (STATUS:CANCELLED)
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.status == TodoStatus::Cancelled
+    }
+
+    /// Pin date-only due and start dates to midnight in `zone`
+    #[must_use]
+    pub fn in_zone<Z: TimeZone>(mut self, zone: &Z) -> Self {
+        if let Some(due) = self.due_date.and_then(|date| midnight_in(date, zone)) {
+            self.due = Some(due);
+        }
+        if let Some(start) = self.start_date.and_then(|date| midnight_in(date, zone)) {
+            self.start = Some(start);
+        }
+        if self.due_date.is_some() || self.start_date.is_some() {
+            self.resolve_alarms();
+        }
+        self
+    }
+
+    /// Recompute relative alarm triggers from the current start and due times
+    pub fn resolve_alarms(&mut self) {
+        for alarm in &mut self.alarms {
+            alarm.resolve(self.start, self.due);
+        }
+    }
+}
+
+/// Status of an event (STATUS)
+///
+/// Serialized with the RFC 5545 names. The spellings stored by older
+/// versions (e.g. `Confirmed`) are still accepted when reading a cache.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+pub enum EventStatus {
+    #[serde(alias = "Tentative")]
+    Tentative,
+    #[serde(alias = "Confirmed")]
+    Confirmed,
+    #[serde(alias = "Cancelled")]
+    Cancelled,
+}
+
+impl EventStatus {
+    /// The RFC 5545 name of the status
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tentative => "TENTATIVE",
+            Self::Confirmed => "CONFIRMED",
+            Self::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+impl FromStr for EventStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "TENTATIVE" => Ok(Self::Tentative),
+            "CONFIRMED" => Ok(Self::Confirmed),
+            "CANCELLED" => Ok(Self::Cancelled),
+            other => anyhow::bail!("Unknown event status: {other}"),
+        }
+    }
+}
+
+impl fmt::Display for EventStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Status of a todo (STATUS)
+///
+/// Serialized with the RFC 5545 names. The spellings stored by older
+/// versions (e.g. `NeedsAction`) are still accepted when reading a cache.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+pub enum TodoStatus {
+    /// The default when a todo has no STATUS
+    #[default]
+    #[serde(alias = "NeedsAction")]
+    NeedsAction,
+    #[serde(alias = "InProcess")]
+    InProcess,
+    #[serde(alias = "Completed")]
+    Completed,
+    #[serde(alias = "Cancelled")]
+    Cancelled,
+}
+
+impl TodoStatus {
+    /// The RFC 5545 name of the status
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NeedsAction => "NEEDS-ACTION",
+            Self::InProcess => "IN-PROCESS",
+            Self::Completed => "COMPLETED",
+            Self::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+impl FromStr for TodoStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "NEEDS-ACTION" => Ok(Self::NeedsAction),
+            "IN-PROCESS" => Ok(Self::InProcess),
+            "COMPLETED" => Ok(Self::Completed),
+            "CANCELLED" => Ok(Self::Cancelled),
+            other => anyhow::bail!("Unknown todo status: {other}"),
+        }
+    }
+}
+
+impl fmt::Display for TodoStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Status of a journal entry (STATUS)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+pub enum JournalStatus {
+    Draft,
+    Final,
+    Cancelled,
+}
+
+impl JournalStatus {
+    /// The RFC 5545 name of the status
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "DRAFT",
+            Self::Final => "FINAL",
+            Self::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+impl FromStr for JournalStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "DRAFT" => Ok(Self::Draft),
+            "FINAL" => Ok(Self::Final),
+            "CANCELLED" => Ok(Self::Cancelled),
+            other => anyhow::bail!("Unknown journal status: {other}"),
+        }
+    }
+}
+
+impl fmt::Display for JournalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents a journal entry (VJOURNAL), such as a daily note
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Journal {
+    /// Unique identifier for the journal entry
+    pub uid: String,
+
+    /// Journal summary/title
+    pub summary: String,
+
+    /// Journal text
+    pub description: Option<String>,
+
+    /// Date the entry belongs to (DTSTART)
+    pub start: Option<DateTime<Utc>>,
+
+    /// Status (DRAFT, FINAL, CANCELLED)
+    pub status: Option<JournalStatus>,
+
+    /// Calendar name this journal entry belongs to
+    pub calendar_name: String,
+
+    /// Calendar URL
+    pub calendar_url: String,
+
+    /// `ETag` for change detection
+    pub etag: Option<String>,
+
+    /// Original timezone of DTSTART, if it had one
+    #[serde(default)]
+    pub tzid: Option<String>,
+
+    /// Date of a date-only DTSTART (floating, like all-day events)
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+
+    /// Categories (CATEGORIES)
+    #[serde(default)]
+    pub categories: Vec<String>,
+
+    /// Link to a page about the entry (URL)
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Access classification (PUBLIC, PRIVATE, CONFIDENTIAL)
+    #[serde(default)]
+    pub class: Option<String>,
+
+    /// When the entry was created (CREATED)
+    #[serde(default)]
+    pub created: Option<DateTime<Utc>>,
+
+    /// When the entry was last modified (LAST-MODIFIED)
+    #[serde(default)]
+    pub last_modified: Option<DateTime<Utc>>,
+
+    /// Href of the calendar object resource the entry was fetched from
+    #[serde(default)]
+    pub href: Option<String>,
+}
+
+impl Journal {
+    /// Pin a date-only start to midnight in `zone`
+    #[must_use]
+    pub fn in_zone<Z: TimeZone>(mut self, zone: &Z) -> Self {
+        if let Some(start) = self.start_date.and_then(|date| midnight_in(date, zone)) {
+            self.start = Some(start);
+        }
+        self
+    }
+}
+
+/// Geographic position of an event (GEO)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Geo {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// An online meeting an event can be joined through
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Conference {
+    /// URI to join the meeting
+    pub uri: String,
+
+    /// Human-readable label (LABEL), if any
+    pub label: Option<String>,
+
+    /// Where the link was found (CONFERENCE, X-GOOGLE-CONFERENCE, URL,
+    /// LOCATION or DESCRIPTION)
+    pub source: String,
+}
+
+/// Organizer of an event (ORGANIZER)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Organizer {
+    /// Calendar user address (usually `mailto:`)
+    pub address: String,
+
+    /// Common name (CN), if any
+    pub name: Option<String>,
+}
+
+/// Participant of an event (ATTENDEE)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Attendee {
+    /// Calendar user address (usually `mailto:`)
+    pub address: String,
+
+    /// Common name (CN), if any
+    pub name: Option<String>,
+
+    /// Participation status (NEEDS-ACTION, ACCEPTED, DECLINED, TENTATIVE, DELEGATED)
+    pub partstat: String,
+
+    /// Role (CHAIR, REQ-PARTICIPANT, OPT-PARTICIPANT, NON-PARTICIPANT)
+    pub role: String,
+
+    /// Whether a reply is expected
+    pub rsvp: bool,
+
+    /// Kind of calendar user (INDIVIDUAL, GROUP, RESOURCE, ROOM, UNKNOWN)
+    pub cutype: String,
+}
+
+/// A reminder attached to an event or todo (VALARM)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Alarm {
+    /// What the alarm does (DISPLAY, AUDIO, EMAIL)
+    pub action: String,
+
+    /// Text to show or send when the alarm fires
+    pub description: Option<String>,
+
+    /// Offset in seconds from the start (or end) for relative triggers
+    pub trigger_offset_seconds: Option<i64>,
+
+    /// What a relative trigger is anchored to (START, or END/DUE)
+    pub trigger_related: Option<String>,
+
+    /// When the alarm fires
+    pub trigger_at: Option<DateTime<Utc>>,
+}
+
+impl Alarm {
+    /// Resolve a relative trigger against the start and end of an occurrence
+    ///
+    /// For todos `end` is the due time. If the anchor is missing the other
+    /// one is used instead. Absolute triggers are left unchanged.
+    pub fn resolve(&mut self, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) {
+        let Some(offset) = self.trigger_offset_seconds else {
+            return;
+        };
+
+        let anchor = if self.trigger_related.as_deref() == Some("END") {
+            end.or(start)
+        } else {
+            start.or(end)
+        };
+
+        self.trigger_at =
+            anchor.and_then(|anchor| anchor.checked_add_signed(chrono::Duration::seconds(offset)));
+    }
+}
+
+/// Midnight at the start of `date` in `zone`, as UTC
+fn midnight_in<Z: TimeZone>(date: NaiveDate, zone: &Z) -> Option<DateTime<Utc>> {
+    zone.from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// The wall-clock times of a query range, as if they were UTC
+///
+/// All-day items are expanded as UTC dates, so they overlap a range in the
+/// caller's zone exactly when they overlap the same wall-clock range in UTC.
+fn floating_range<Z: TimeZone>(
+    start: &DateTime<Z>,
+    end: &DateTime<Z>,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    (start.naive_local().and_utc(), end.naive_local().and_utc())
+}
+
+/// Items found in a date range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeResult<T> {
+    /// Events or todos in the range
+    pub items: Vec<T>,
+
+    /// UIDs of recurring series that had more occurrences than allowed
+    pub truncated_series: Vec<String>,
 }
 
 /// Container for all calendar data
@@ -101,6 +708,10 @@ pub struct CalendarData {
     /// All todos
     pub todos: Vec<Todo>,
 
+    /// All journal entries
+    #[serde(default)]
+    pub journals: Vec<Journal>,
+
     /// Last sync timestamp
     pub last_sync: DateTime<Utc>,
 
@@ -108,6 +719,49 @@ pub struct CalendarData {
     /// Maps calendar URL to sync token
     #[serde(default)]
     pub sync_tokens: std::collections::HashMap<String, String>,
+
+    /// Change tags per calendar as of their last successful sync
+    /// Maps calendar URL to its `CTag` (getctag) or collection sync-token
+    #[serde(default)]
+    pub ctags: std::collections::HashMap<String, String>,
+
+    /// Original iCalendar source of every calendar object resource
+    /// Maps resource href to the object
+    #[serde(default)]
+    pub objects: std::collections::HashMap<String, CalendarObject>,
+
+    /// Format version the data was cached with
+    /// Caches written before versions were recorded read as `0`
+    #[serde(default)]
+    pub version: u32,
+}
+
+/// Current version of the cached data format
+///
+/// Bump it when cached items have to be fetched again, e.g. because they
+/// are stored differently.
+pub const CACHE_VERSION: u32 = 6;
+
+/// A calendar object resource as it was fetched from the server
+///
+/// Keeps the raw iCalendar text, including properties and components the
+/// models don't map, for debugging and writing changes back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CalendarObject {
+    /// Href of the resource on the server
+    pub href: String,
+
+    /// URL of the calendar containing the resource
+    pub calendar_url: String,
+
+    /// `ETag` of the resource when it was fetched
+    pub etag: Option<String>,
+
+    /// UIDs of the components in the resource
+    pub uids: Vec<String>,
+
+    /// The iCalendar text returned by the server
+    pub ics: String,
 }
 
 impl CalendarData {
@@ -117,52 +771,454 @@ impl CalendarData {
         Self {
             events: Vec::new(),
             todos: Vec::new(),
+            journals: Vec::new(),
             last_sync: Utc::now(),
             sync_tokens: std::collections::HashMap::new(),
+            ctags: std::collections::HashMap::new(),
+            objects: std::collections::HashMap::new(),
+            version: CACHE_VERSION,
         }
     }
 
-    /// Get events for a specific date range
+    /// Drop everything stored for calendars not in `calendar_urls`
+    ///
+    /// Returns the number of removed (events, todos, journals).
+    pub fn retain_calendars(
+        &mut self,
+        calendar_urls: &std::collections::HashSet<String>,
+    ) -> (usize, usize, usize) {
+        let initial = (self.events.len(), self.todos.len(), self.journals.len());
+
+        self.events
+            .retain(|e| calendar_urls.contains(&e.calendar_url));
+        self.todos
+            .retain(|t| calendar_urls.contains(&t.calendar_url));
+        self.journals
+            .retain(|j| calendar_urls.contains(&j.calendar_url));
+        self.sync_tokens
+            .retain(|url, _| calendar_urls.contains(url));
+        self.ctags.retain(|url, _| calendar_urls.contains(url));
+        self.objects
+            .retain(|_, o| calendar_urls.contains(&o.calendar_url));
+
+        (
+            initial.0 - self.events.len(),
+            initial.1 - self.todos.len(),
+            initial.2 - self.journals.len(),
+        )
+    }
+
+    /// Find the calendar object resource containing the component with `uid`
+    ///
+    /// If several resources claim the UID, the one with the lowest href wins
+    /// so the result is stable.
     #[must_use]
-    pub fn events_in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&CalendarEvent> {
+    pub fn object_for_uid(&self, uid: &str) -> Option<&CalendarObject> {
+        self.objects
+            .values()
+            .filter(|object| object.uids.iter().any(|u| u == uid))
+            .min_by(|a, b| a.href.cmp(&b.href))
+    }
+
+    /// Remove everything stored for the calendar object resource at `href`
+    ///
+    /// Items are matched by the href they were fetched from. Items cached
+    /// before hrefs were tracked are matched by the UIDs recorded for the
+    /// resource, or by a file name of exactly `{uid}.ics`.
+    ///
+    /// Returns the number of removed events, todos and journal entries.
+    pub fn remove_resource(&mut self, href: &str) -> usize {
+        let object = self.objects.remove(href);
+        let file_name = href.rsplit('/').next().unwrap_or(href);
+        // Items cached before hrefs were tracked
+        let legacy = |uid: &str| {
+            object
+                .as_ref()
+                .is_some_and(|object| object.uids.iter().any(|u| u == uid))
+                || file_name.strip_suffix(".ics") == Some(uid)
+        };
+        let belongs = |item_href: Option<&str>, uid: &str| {
+            item_href.map_or_else(|| legacy(uid), |item_href| item_href == href)
+        };
+
+        let initial = self.events.len() + self.todos.len() + self.journals.len();
+        self.events.retain(|e| !belongs(e.href.as_deref(), &e.uid));
+        self.todos.retain(|t| !belongs(t.href.as_deref(), &t.uid));
+        self.journals
+            .retain(|j| !belongs(j.href.as_deref(), &j.uid));
+
+        initial - (self.events.len() + self.todos.len() + self.journals.len())
+    }
+
+    /// Hrefs of the resources in a calendar holding any of `components`
+    ///
+    /// `components` are component names such as `VEVENT` or `VTODO`.
+    #[must_use]
+    pub fn resource_hrefs(
+        &self,
+        calendar_url: &str,
+        components: &[&str],
+    ) -> std::collections::HashSet<String> {
+        let mut hrefs = std::collections::HashSet::new();
+        let mut add = |href: Option<&String>, item_calendar: &str| {
+            if let Some(href) = href
+                && item_calendar == calendar_url
+            {
+                hrefs.insert(href.clone());
+            }
+        };
+
+        if components.contains(&"VEVENT") {
+            self.events
+                .iter()
+                .for_each(|e| add(e.href.as_ref(), &e.calendar_url));
+        }
+        if components.contains(&"VTODO") {
+            self.todos
+                .iter()
+                .for_each(|t| add(t.href.as_ref(), &t.calendar_url));
+        }
+        if components.contains(&"VJOURNAL") {
+            self.journals
+                .iter()
+                .for_each(|j| add(j.href.as_ref(), &j.calendar_url));
+        }
+
+        hrefs
+    }
+
+    /// Drop the resources of a calendar whose href isn't in `hrefs`
+    ///
+    /// Used once the server has listed every resource of the calendar.
+    /// Items without an href are dropped too, as every listed resource has
+    /// just been fetched again.
+    ///
+    /// Returns the number of removed events, todos and journal entries.
+    pub fn retain_resources(
+        &mut self,
+        calendar_url: &str,
+        hrefs: &std::collections::HashSet<String>,
+    ) -> usize {
+        let keep = |item_calendar: &str, item_href: Option<&String>| {
+            item_calendar != calendar_url || item_href.is_some_and(|href| hrefs.contains(href))
+        };
+
+        let initial = self.events.len() + self.todos.len() + self.journals.len();
         self.events
-            .iter()
-            .filter(|event| {
-                // Event overlaps with the range if:
-                // - Event starts before range ends AND
-                // - Event ends after range starts
-                event.start < end && event.end > start
-            })
-            .collect()
+            .retain(|e| keep(&e.calendar_url, e.href.as_ref()));
+        self.todos
+            .retain(|t| keep(&t.calendar_url, t.href.as_ref()));
+        self.journals
+            .retain(|j| keep(&j.calendar_url, j.href.as_ref()));
+        self.objects
+            .retain(|href, o| o.calendar_url != calendar_url || hrefs.contains(href));
+
+        initial - (self.events.len() + self.todos.len() + self.journals.len())
+    }
+
+    /// Replace everything stored for a calendar object resource
+    ///
+    /// A resource holds the master and all overrides of a series, so the
+    /// items previously fetched from `object.href` are dropped together,
+    /// including ones no longer in the resource. Items cached before hrefs
+    /// were tracked are replaced by UID.
+    pub fn replace_resource(
+        &mut self,
+        object: CalendarObject,
+        events: Vec<CalendarEvent>,
+        todos: Vec<Todo>,
+        journals: Vec<Journal>,
+    ) {
+        self.remove_resource(&object.href);
+
+        let replaced = |item_href: Option<&str>, uid: &str| {
+            item_href.is_none() && object.uids.iter().any(|u| u == uid)
+        };
+        self.events.retain(|e| !replaced(e.href.as_deref(), &e.uid));
+        self.todos.retain(|t| !replaced(t.href.as_deref(), &t.uid));
+        self.journals
+            .retain(|j| !replaced(j.href.as_deref(), &j.uid));
+
+        self.events.extend(events);
+        self.todos.extend(todos);
+        self.journals.extend(journals);
+        self.objects.insert(object.href.clone(), object);
+    }
+
+    /// Get events for a specific date range
+    ///
+    /// Recurring series are expanded on demand for exactly the requested
+    /// range, with their overrides (RECURRENCE-ID) applied. Overrides whose
+    /// master is unknown are returned as standalone events. All-day events
+    /// are treated as floating dates in the zone of `start`. Expansion uses
+    /// the default [`RecurrenceConfig`].
+    #[must_use]
+    pub fn events_in_range<Z: TimeZone>(
+        &self,
+        start: DateTime<Z>,
+        end: DateTime<Z>,
+    ) -> Vec<CalendarEvent> {
+        self.events_in_range_with(start, end, &RecurrenceConfig::default())
+            .items
+    }
+
+    /// Get events for a specific date range, expanding series with `config`
+    ///
+    /// Occurrences of recurring series are only generated inside the
+    /// configured window, and series with more occurrences than the cap are
+    /// reported in `truncated_series`.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn events_in_range_with<Z: TimeZone>(
+        &self,
+        start: DateTime<Z>,
+        end: DateTime<Z>,
+        config: &RecurrenceConfig,
+    ) -> RangeResult<CalendarEvent> {
+        let zone = start.timezone();
+        let floating = floating_range(&start, &end);
+        let (start, end) = (start.with_timezone(&Utc), end.with_timezone(&Utc));
+        let now = Utc::now();
+
+        // Group overrides by the series they belong to
+        let mut overrides: HashMap<(&str, &str), Vec<&CalendarEvent>> = HashMap::new();
+        for event in self.events.iter().filter(|e| e.recurrence_id.is_some()) {
+            overrides
+                .entry((event.calendar_url.as_str(), event.uid.as_str()))
+                .or_default()
+                .push(event);
+        }
+
+        let mut events = Vec::new();
+        let mut truncated_series = Vec::new();
+
+        for event in self.events.iter().filter(|e| e.recurrence_id.is_none()) {
+            let series_overrides = overrides
+                .remove(&(event.calendar_url.as_str(), event.uid.as_str()))
+                .unwrap_or_default();
+
+            // All-day events are expanded as dates, so only occurrences in
+            // the requested range count towards the cap
+            let (event_start, event_end) = if event.start_date.is_some() {
+                floating
+            } else {
+                (start, end)
+            };
+
+            // Only recurring series are held to the expansion window
+            let is_series = event.rrule.is_some() || !event.rdates.is_empty();
+            let range = if is_series {
+                config.clamp_range(event_start, event_end, now)
+            } else {
+                Some((event_start, event_end))
+            };
+            let Some((series_start, series_end)) = range else {
+                continue;
+            };
+
+            let (instances, truncated) = expand_recurring_event_limited(
+                event,
+                &series_overrides,
+                series_start,
+                series_end,
+                config.max_instances,
+            );
+            if truncated {
+                truncated_series.push(event.uid.clone());
+            }
+            events.extend(instances);
+        }
+
+        // Whatever is left are overrides without a master
+        events.extend(
+            overrides
+                .into_values()
+                .flatten()
+                .filter(|orphan| !orphan.is_cancelled())
+                .cloned(),
+        );
+
+        RangeResult {
+            items: events
+                .into_iter()
+                .map(|event| event.in_zone(&zone))
+                .filter(|event| overlaps(event.start, event.end, start, end))
+                .collect(),
+            truncated_series,
+        }
     }
 
     /// Get todos due in a specific date range
+    ///
+    /// Recurring todos contribute every pending occurrence due in the range;
+    /// occurrences completed through an override or by rolling the series
+    /// forward are left out. Date-only due and start dates are treated as
+    /// floating dates in the zone of `start`. Expansion uses the default
+    /// [`RecurrenceConfig`].
     #[must_use]
-    #[allow(clippy::option_if_let_else)]
-    pub fn todos_in_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&Todo> {
-        self.todos
-            .iter()
-            .filter(|todo| {
-                if let Some(due) = todo.due {
-                    due >= start && due < end
-                } else if let Some(start_date) = todo.start {
-                    start_date >= start && start_date < end
+    pub fn todos_in_range<Z: TimeZone>(&self, start: DateTime<Z>, end: DateTime<Z>) -> Vec<Todo> {
+        self.todos_in_range_with(start, end, &RecurrenceConfig::default())
+            .items
+    }
+
+    /// Get todos due in a specific date range, expanding series with `config`
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn todos_in_range_with<Z: TimeZone>(
+        &self,
+        start: DateTime<Z>,
+        end: DateTime<Z>,
+        config: &RecurrenceConfig,
+    ) -> RangeResult<Todo> {
+        let zone = start.timezone();
+        let floating = floating_range(&start, &end);
+        let (start, end) = (start.with_timezone(&Utc), end.with_timezone(&Utc));
+
+        let mut overrides = self.todo_overrides();
+        let mut todos = Vec::new();
+        let mut truncated_series = Vec::new();
+
+        for todo in self.todos.iter().filter(|t| t.recurrence_id.is_none()) {
+            if todo.rrule.is_some() {
+                let series_overrides = overrides
+                    .remove(&(todo.calendar_url.as_str(), todo.uid.as_str()))
+                    .unwrap_or_default();
+                // Date-only todos are expanded as dates
+                let date_only = if todo.due.is_some() {
+                    todo.due_date.is_some()
                 } else {
-                    // Include todos without dates in all ranges
-                    true
+                    todo.start_date.is_some()
+                };
+                let (todo_start, todo_end) = if date_only { floating } else { (start, end) };
+                let Some((series_start, series_end)) =
+                    config.clamp_range(todo_start, todo_end, Utc::now())
+                else {
+                    continue;
+                };
+
+                let (occurrences, truncated) = expand_recurring_todo_limited(
+                    todo,
+                    &series_overrides,
+                    series_start,
+                    series_end,
+                    config.max_instances,
+                );
+                if truncated {
+                    truncated_series.push(todo.uid.clone());
                 }
-            })
-            .collect()
+                todos.extend(occurrences);
+            } else {
+                todos.push(todo.clone());
+            }
+        }
+
+        // Overrides of series we don't know about are shown as they are
+        todos.extend(overrides.into_values().flatten().cloned());
+
+        // A todo is placed by its due date, or its start date without one;
+        // todos without dates are included in all ranges
+        RangeResult {
+            items: todos
+                .into_iter()
+                .map(|todo| todo.in_zone(&zone))
+                .filter(|todo| {
+                    todo.due
+                        .or(todo.start)
+                        .is_none_or(|d| d >= start && d < end)
+                })
+                .collect(),
+            truncated_series,
+        }
     }
 
     /// Get all incomplete todos
+    ///
+    /// A recurring todo is reported once, as its next pending occurrence.
     #[must_use]
     #[allow(dead_code)]
-    pub fn incomplete_todos(&self) -> Vec<&Todo> {
-        self.todos
+    pub fn incomplete_todos(&self) -> Vec<Todo> {
+        let mut overrides = self.todo_overrides();
+        let mut todos = Vec::new();
+
+        for todo in self.todos.iter().filter(|t| t.recurrence_id.is_none()) {
+            if todo.rrule.is_some() {
+                let series_overrides = overrides
+                    .remove(&(todo.calendar_url.as_str(), todo.uid.as_str()))
+                    .unwrap_or_default();
+                todos.extend(next_pending_todo(todo, &series_overrides));
+            } else if !todo.is_completed() && !todo.is_cancelled() {
+                todos.push(todo.clone());
+            }
+        }
+
+        for orphan in overrides.into_values().flatten() {
+            if !orphan.is_completed() && !orphan.is_cancelled() {
+                todos.push(orphan.clone());
+            }
+        }
+
+        todos
+    }
+
+    /// Get journal entries dated in a specific range, oldest first
+    ///
+    /// Date-only entries are placed in the zone of `start`. Entries without
+    /// a date are left out.
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn journals_in_range<Z: TimeZone>(
+        &self,
+        start: DateTime<Z>,
+        end: DateTime<Z>,
+    ) -> Vec<Journal> {
+        let zone = start.timezone();
+        let (start, end) = (start.with_timezone(&Utc), end.with_timezone(&Utc));
+
+        let mut journals: Vec<Journal> = self
+            .journals
             .iter()
-            .filter(|todo| todo.status != "COMPLETED" && todo.status != "CANCELLED")
-            .collect()
+            .map(|journal| journal.clone().in_zone(&zone))
+            .filter(|journal| journal.start.is_some_and(|d| d >= start && d < end))
+            .collect();
+        journals.sort_by_key(|journal| journal.start);
+        journals
+    }
+
+    /// Get the current state of every todo, including finished ones
+    ///
+    /// A recurring todo is reported once, as its next pending occurrence;
+    /// series without pending occurrences are left out.
+    #[must_use]
+    pub fn current_todos(&self) -> Vec<Todo> {
+        let mut overrides = self.todo_overrides();
+        let mut todos = Vec::new();
+
+        for todo in self.todos.iter().filter(|t| t.recurrence_id.is_none()) {
+            if todo.rrule.is_some() {
+                let series_overrides = overrides
+                    .remove(&(todo.calendar_url.as_str(), todo.uid.as_str()))
+                    .unwrap_or_default();
+                todos.extend(next_pending_todo(todo, &series_overrides));
+            } else {
+                todos.push(todo.clone());
+            }
+        }
+
+        todos.extend(overrides.into_values().flatten().cloned());
+
+        todos
+    }
+
+    /// Group todo overrides (RECURRENCE-ID) by the series they belong to
+    fn todo_overrides(&self) -> HashMap<(&str, &str), Vec<&Todo>> {
+        let mut overrides: HashMap<(&str, &str), Vec<&Todo>> = HashMap::new();
+        for todo in self.todos.iter().filter(|t| t.recurrence_id.is_some()) {
+            overrides
+                .entry((todo.calendar_url.as_str(), todo.uid.as_str()))
+                .or_default()
+                .push(todo);
+        }
+        overrides
     }
 }
 
@@ -210,13 +1266,36 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         };
 
         let event2 = CalendarEvent {
             uid: "2".to_string(),
-            summary: "Event 2".to_string(),
-            description: None,
-            location: None,
+            summary: "Event 2".to_string(),
             start: Utc
                 .with_ymd_and_hms(2026, 1, 10, 14, 0, 0)
                 .single()
@@ -225,14 +1304,7 @@ mod tests {
                 .with_ymd_and_hms(2026, 1, 10, 15, 0, 0)
                 .single()
                 .expect("valid datetime"),
-            calendar_name: "Test".to_string(),
-            calendar_url: "/test".to_string(),
-            calendar_color: None,
-            all_day: false,
-            rrule: None,
-            exdates: Vec::new(),
-            status: None,
-            etag: None,
+            ..event1.clone()
         };
 
         data.events.push(event1);
@@ -269,10 +1341,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         let todo2 = Todo {
@@ -288,10 +1376,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         data.todos.push(todo1);
@@ -324,10 +1428,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         let todo2 = Todo {
@@ -339,10 +1459,26 @@ mod tests {
             completed: Some(Utc::now()),
             priority: None,
             percent_complete: Some(100),
-            status: "COMPLETED".to_string(),
+            status: TodoStatus::Completed,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         data.todos.push(todo1);
@@ -379,6 +1515,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         };
 
         data.events.push(all_day_event);
@@ -427,14 +1588,37 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         };
 
         // Event that ends at midnight on query start (should not overlap)
         let ends_at_midnight = CalendarEvent {
             uid: "ends-midnight".to_string(),
             summary: "Ends at Midnight".to_string(),
-            description: None,
-            location: None,
             start: Utc
                 .with_ymd_and_hms(2026, 1, 2, 0, 0, 0)
                 .single()
@@ -443,14 +1627,7 @@ mod tests {
                 .with_ymd_and_hms(2026, 1, 3, 0, 0, 0)
                 .single()
                 .expect("valid datetime"),
-            calendar_name: "Test".to_string(),
-            calendar_url: "/test".to_string(),
-            calendar_color: None,
-            all_day: true,
-            rrule: None,
-            exdates: Vec::new(),
-            status: None,
-            etag: None,
+            ..single_day.clone()
         };
 
         data.events.push(single_day);
@@ -491,10 +1668,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "IN-PROCESS".to_string(),
+            status: TodoStatus::InProcess,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         data.todos.push(todo);
@@ -527,10 +1720,26 @@ mod tests {
             completed: None,
             priority: Some(1),
             percent_complete: Some(0),
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: Some("etag123".to_string()),
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         data.todos.push(todo);
@@ -571,8 +1780,33 @@ mod tests {
             all_day: false,
             rrule: None,
             exdates: Vec::new(),
-            status: Some("Confirmed".to_string()),
+            status: Some(EventStatus::Confirmed),
             etag: Some("etag123".to_string()),
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         };
 
         let event2 = event1.clone();
@@ -602,10 +1836,26 @@ mod tests {
             completed: Some(Utc::now()),
             priority: Some(5),
             percent_complete: Some(75),
-            status: "IN-PROCESS".to_string(),
+            status: TodoStatus::InProcess,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: Some("etag1".to_string()),
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         let todo2 = todo1.clone();
@@ -642,6 +1892,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         });
         data.sync_tokens
             .insert("calendar1".to_string(), "token123".to_string());
@@ -678,6 +1953,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         };
 
         let debug_str = format!("{event:?}");
@@ -696,10 +1996,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         let debug_str = format!("{todo:?}");
@@ -727,10 +2043,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         let todo2 = Todo {
@@ -742,10 +2074,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "CANCELLED".to_string(),
+            status: TodoStatus::Cancelled,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         data.todos.push(todo1);
@@ -753,7 +2101,7 @@ mod tests {
 
         let incomplete = data.incomplete_todos();
         assert_eq!(incomplete.len(), 1);
-        assert_eq!(incomplete[0].status, "NEEDS-ACTION");
+        assert_eq!(incomplete[0].status, TodoStatus::NeedsAction);
     }
 
     #[test]
@@ -802,14 +2150,37 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         };
 
         // Event that ends exactly at range end
         let event2 = CalendarEvent {
             uid: "2".to_string(),
             summary: "Ends at boundary".to_string(),
-            description: None,
-            location: None,
             start: Utc
                 .with_ymd_and_hms(2026, 1, 5, 23, 0, 0)
                 .single()
@@ -818,14 +2189,7 @@ mod tests {
                 .with_ymd_and_hms(2026, 1, 6, 0, 0, 0)
                 .single()
                 .expect("valid datetime"),
-            calendar_name: "Test".to_string(),
-            calendar_url: "/test".to_string(),
-            calendar_color: None,
-            all_day: false,
-            rrule: None,
-            exdates: Vec::new(),
-            status: None,
-            etag: None,
+            ..event1.clone()
         };
 
         data.events.push(event1);
@@ -862,10 +2226,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         // Todo due exactly at range end (should not be included)
@@ -882,10 +2262,26 @@ mod tests {
             completed: None,
             priority: None,
             percent_complete: None,
-            status: "NEEDS-ACTION".to_string(),
+            status: TodoStatus::NeedsAction,
             calendar_name: "Tasks".to_string(),
             calendar_url: "/tasks".to_string(),
             etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
         };
 
         data.todos.push(todo1);
@@ -905,4 +2301,584 @@ mod tests {
         assert_eq!(filtered_todos.len(), 1);
         assert_eq!(filtered_todos[0].uid, "1");
     }
+
+    fn recurring_test_event(uid: &str, start: DateTime<Utc>, rrule: Option<&str>) -> CalendarEvent {
+        CalendarEvent {
+            uid: uid.to_string(),
+            summary: uid.to_string(),
+            description: None,
+            location: None,
+            start,
+            end: start + chrono::Duration::hours(1),
+            calendar_name: "Test".to_string(),
+            calendar_url: "/test".to_string(),
+            calendar_color: None,
+            all_day: false,
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            rrule: rrule.map(String::from),
+            exdates: Vec::new(),
+            rdates: Vec::new(),
+            status: None,
+            etag: None,
+            recurrence_id: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
+        }
+    }
+
+    #[test]
+    fn test_events_in_range_expands_recurring_series() {
+        let mut data = CalendarData::new();
+        let start = Utc
+            .with_ymd_and_hms(2026, 1, 5, 10, 0, 0)
+            .single()
+            .expect("valid datetime");
+        data.events
+            .push(recurring_test_event("weekly", start, Some("FREQ=WEEKLY")));
+
+        // Only the master is stored, but every week in range is returned
+        let range_start = Utc
+            .with_ymd_and_hms(2029, 1, 1, 0, 0, 0)
+            .single()
+            .expect("valid datetime");
+        let range_end = range_start + chrono::Duration::weeks(3);
+
+        let events = data.events_in_range(range_start, range_end);
+        assert_eq!(data.events.len(), 1);
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| e.uid == "weekly"));
+    }
+
+    #[test]
+    fn test_events_in_range_applies_overrides() {
+        let mut data = CalendarData::new();
+        let start = Utc
+            .with_ymd_and_hms(2026, 1, 5, 10, 0, 0)
+            .single()
+            .expect("valid datetime");
+        data.events.push(recurring_test_event(
+            "standup",
+            start,
+            Some("FREQ=DAILY;COUNT=3"),
+        ));
+
+        let moved_original = start + chrono::Duration::days(1);
+        let mut moved =
+            recurring_test_event("standup", moved_original + chrono::Duration::hours(2), None);
+        moved.summary = "Standup (moved)".to_string();
+        moved.recurrence_id = Some(moved_original);
+        data.events.push(moved);
+
+        let cancelled_original = start + chrono::Duration::days(2);
+        let mut cancelled = recurring_test_event("standup", cancelled_original, None);
+        cancelled.recurrence_id = Some(cancelled_original);
+        cancelled.status = Some(EventStatus::Cancelled);
+        data.events.push(cancelled);
+
+        let events = data.events_in_range(start, start + chrono::Duration::days(7));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].start, start);
+        assert_eq!(events[1].summary, "Standup (moved)");
+    }
+
+    #[test]
+    fn test_events_in_range_orphan_overrides() {
+        let mut data = CalendarData::new();
+        let start = Utc
+            .with_ymd_and_hms(2026, 1, 5, 10, 0, 0)
+            .single()
+            .expect("valid datetime");
+
+        // Invitation to a single occurrence of a series we don't have
+        let mut orphan = recurring_test_event("invite", start, None);
+        orphan.recurrence_id = Some(start);
+        data.events.push(orphan);
+
+        let mut cancelled = recurring_test_event("cancelled-invite", start, None);
+        cancelled.recurrence_id = Some(start);
+        cancelled.status = Some(EventStatus::Cancelled);
+        data.events.push(cancelled);
+
+        let events = data.events_in_range(start, start + chrono::Duration::days(1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].uid, "invite");
+    }
+
+    #[test]
+    fn test_make_instance_id() {
+        let rid = Utc
+            .with_ymd_and_hms(2026, 1, 5, 10, 0, 0)
+            .single()
+            .expect("valid datetime");
+
+        assert_eq!(CalendarEvent::make_instance_id("abc", None), "abc");
+        assert_eq!(
+            CalendarEvent::make_instance_id("abc", Some(rid)),
+            "abc:20260105T100000Z"
+        );
+    }
+
+    #[test]
+    fn test_recurring_todos_in_range_and_incomplete() {
+        let mut data = CalendarData::new();
+        let due = Utc
+            .with_ymd_and_hms(2026, 1, 1, 17, 0, 0)
+            .single()
+            .expect("valid datetime");
+
+        data.todos.push(Todo {
+            uid: "rent".to_string(),
+            summary: "Pay rent".to_string(),
+            description: None,
+            due: Some(due),
+            start: None,
+            completed: Some(due),
+            priority: None,
+            percent_complete: None,
+            status: TodoStatus::Completed,
+            calendar_name: "Tasks".to_string(),
+            calendar_url: "/tasks".to_string(),
+            etag: None,
+            rrule: Some("FREQ=MONTHLY".to_string()),
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
+        });
+
+        let range_start = Utc
+            .with_ymd_and_hms(2026, 1, 1, 0, 0, 0)
+            .single()
+            .expect("valid datetime");
+        let range_end = Utc
+            .with_ymd_and_hms(2026, 3, 1, 0, 0, 0)
+            .single()
+            .expect("valid datetime");
+
+        // January was completed, so only February is pending in the range
+        let in_range = data.todos_in_range(range_start, range_end);
+        assert_eq!(in_range.len(), 1);
+        assert_eq!(
+            in_range[0].due,
+            Some(
+                Utc.with_ymd_and_hms(2026, 2, 1, 17, 0, 0)
+                    .single()
+                    .expect("valid datetime")
+            )
+        );
+
+        // The completed series still has a pending next occurrence
+        let incomplete = data.incomplete_todos();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].due, in_range[0].due);
+    }
+
+    fn all_day_test_event(uid: &str, date: NaiveDate, rrule: Option<&str>) -> CalendarEvent {
+        let start = Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN));
+        let end = start + chrono::Duration::days(1);
+        CalendarEvent {
+            all_day: true,
+            end,
+            start_date: Some(start.date_naive()),
+            end_date: Some(end.date_naive()),
+            ..recurring_test_event(uid, start, rrule)
+        }
+    }
+
+    #[test]
+    fn test_events_in_range_all_day_in_callers_zone() {
+        let mut data = CalendarData::new();
+        let event_date = NaiveDate::from_ymd_opt(2026, 3, 5).expect("valid date");
+        data.events
+            .push(all_day_test_event("holiday", event_date, None));
+
+        // Eight hours west of Greenwich
+        let zone = chrono::FixedOffset::west_opt(8 * 3600).expect("valid offset");
+        let day = |d: u32| {
+            zone.with_ymd_and_hms(2026, 3, d, 0, 0, 0)
+                .single()
+                .expect("valid datetime")
+        };
+
+        let events = data.events_in_range(day(5), day(6));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start, day(5));
+        assert_eq!(events[0].end, day(6));
+        assert_eq!(events[0].start_date, Some(event_date));
+
+        assert!(data.events_in_range(day(4), day(5)).is_empty());
+        assert!(data.events_in_range(day(6), day(7)).is_empty());
+    }
+
+    #[test]
+    fn test_events_in_range_recurring_all_day_in_callers_zone() {
+        let mut data = CalendarData::new();
+        let event_date = NaiveDate::from_ymd_opt(2026, 3, 5).expect("valid date");
+        data.events.push(all_day_test_event(
+            "weekly",
+            event_date,
+            Some("FREQ=WEEKLY"),
+        ));
+
+        // Fourteen hours east of Greenwich
+        let zone = chrono::FixedOffset::east_opt(14 * 3600).expect("valid offset");
+        let start = zone
+            .with_ymd_and_hms(2026, 3, 12, 0, 0, 0)
+            .single()
+            .expect("valid datetime");
+        let end = start + chrono::Duration::days(1);
+
+        let events = data.events_in_range(start, end);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start, start);
+        assert_eq!(events[0].start_date, NaiveDate::from_ymd_opt(2026, 3, 12));
+    }
+
+    #[test]
+    fn test_todos_in_range_due_date_in_callers_zone() {
+        let mut data = CalendarData::new();
+        let event_date = NaiveDate::from_ymd_opt(2026, 3, 5).expect("valid date");
+        data.todos.push(Todo {
+            uid: "taxes".to_string(),
+            summary: "File taxes".to_string(),
+            description: None,
+            due: Some(Utc.from_utc_datetime(&event_date.and_time(NaiveTime::MIN))),
+            start: None,
+            completed: None,
+            priority: None,
+            percent_complete: None,
+            status: TodoStatus::NeedsAction,
+            calendar_name: "Tasks".to_string(),
+            calendar_url: "/tasks".to_string(),
+            etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: Some(event_date),
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
+        });
+
+        let zone = chrono::FixedOffset::west_opt(5 * 3600).expect("valid offset");
+        let day = |d: u32| {
+            zone.with_ymd_and_hms(2026, 3, d, 0, 0, 0)
+                .single()
+                .expect("valid datetime")
+        };
+
+        let todos = data.todos_in_range(day(5), day(6));
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].due, Some(day(5).with_timezone(&Utc)));
+        assert!(data.todos_in_range(day(4), day(5)).is_empty());
+    }
+
+    #[test]
+    fn test_events_in_range_with_reports_truncated_series() {
+        let mut data = CalendarData::new();
+        let start = Utc
+            .with_ymd_and_hms(2026, 1, 5, 10, 0, 0)
+            .single()
+            .expect("valid datetime");
+        data.events
+            .push(recurring_test_event("daily", start, Some("FREQ=DAILY")));
+        data.events
+            .push(recurring_test_event("weekly", start, Some("FREQ=WEEKLY")));
+
+        let config = RecurrenceConfig {
+            past_days: None,
+            future_days: None,
+            max_instances: Some(5),
+        };
+        let result = data.events_in_range_with(start, start + chrono::Duration::days(28), &config);
+
+        assert_eq!(result.truncated_series, vec!["daily".to_string()]);
+        assert_eq!(result.items.iter().filter(|e| e.uid == "daily").count(), 5);
+        assert_eq!(result.items.iter().filter(|e| e.uid == "weekly").count(), 4);
+    }
+
+    #[test]
+    fn test_events_in_range_with_caps_only_requested_range() {
+        let mut data = CalendarData::new();
+        let start = Utc
+            .with_ymd_and_hms(2026, 1, 5, 10, 0, 0)
+            .single()
+            .expect("valid datetime");
+        let mut event = recurring_test_event(
+            "every-minute",
+            start - chrono::Duration::days(2),
+            Some("FREQ=MINUTELY"),
+        );
+        event.end = event.start + chrono::Duration::minutes(1);
+        data.events.push(event);
+
+        // The day before the range holds more occurrences than the cap
+        let result = data.events_in_range_with(
+            start,
+            start + chrono::Duration::hours(1),
+            &RecurrenceConfig::default(),
+        );
+
+        assert_eq!(result.items.len(), 60);
+        assert!(result.truncated_series.is_empty());
+    }
+
+    #[test]
+    fn test_events_in_range_with_window_only_limits_series() {
+        let mut data = CalendarData::new();
+        let now = Utc::now();
+        // Offset by half a day so no occurrence sits on a window boundary
+        let series_start = now - chrono::Duration::hours(12);
+        data.events.push(recurring_test_event(
+            "daily",
+            series_start,
+            Some("FREQ=DAILY"),
+        ));
+        let far_future = now + chrono::Duration::days(400);
+        data.events
+            .push(recurring_test_event("one-off", far_future, None));
+
+        let config = RecurrenceConfig {
+            past_days: Some(0),
+            future_days: Some(30),
+            max_instances: None,
+        };
+        let result = data.events_in_range_with(
+            now - chrono::Duration::days(1),
+            now + chrono::Duration::days(500),
+            &config,
+        );
+
+        // The series stops at the window, single events are always returned
+        assert_eq!(result.items.iter().filter(|e| e.uid == "daily").count(), 30);
+        assert!(result.items.iter().any(|e| e.uid == "one-off"));
+        assert!(result.truncated_series.is_empty());
+    }
+
+    #[test]
+    fn test_status_serialization_uses_rfc_names() {
+        assert_eq!(
+            serde_json::to_string(&TodoStatus::NeedsAction).unwrap(),
+            "\"NEEDS-ACTION\""
+        );
+        assert_eq!(
+            serde_json::to_string(&TodoStatus::InProcess).unwrap(),
+            "\"IN-PROCESS\""
+        );
+        assert_eq!(
+            serde_json::to_string(&EventStatus::Confirmed).unwrap(),
+            "\"CONFIRMED\""
+        );
+        assert_eq!(
+            serde_json::to_string(&JournalStatus::Final).unwrap(),
+            "\"FINAL\""
+        );
+    }
+
+    #[test]
+    fn test_status_accepts_legacy_cache_values() {
+        for (value, expected) in [
+            ("\"NEEDS-ACTION\"", TodoStatus::NeedsAction),
+            ("\"NeedsAction\"", TodoStatus::NeedsAction),
+            ("\"InProcess\"", TodoStatus::InProcess),
+            ("\"Completed\"", TodoStatus::Completed),
+            ("\"Cancelled\"", TodoStatus::Cancelled),
+        ] {
+            assert_eq!(serde_json::from_str::<TodoStatus>(value).unwrap(), expected);
+        }
+
+        for (value, expected) in [
+            ("\"Tentative\"", EventStatus::Tentative),
+            ("\"Confirmed\"", EventStatus::Confirmed),
+            ("\"CANCELLED\"", EventStatus::Cancelled),
+        ] {
+            assert_eq!(
+                serde_json::from_str::<EventStatus>(value).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_status_from_str() {
+        assert_eq!(
+            "in-process".parse::<TodoStatus>().unwrap(),
+            TodoStatus::InProcess
+        );
+        assert_eq!(
+            " Confirmed ".parse::<EventStatus>().unwrap(),
+            EventStatus::Confirmed
+        );
+        assert_eq!(
+            "draft".parse::<JournalStatus>().unwrap(),
+            JournalStatus::Draft
+        );
+        assert!("X-WAITING".parse::<TodoStatus>().is_err());
+        assert_eq!(TodoStatus::Completed.to_string(), "COMPLETED");
+    }
+
+    fn test_object(href: &str, uids: &[&str]) -> CalendarObject {
+        CalendarObject {
+            href: href.to_string(),
+            calendar_url: "/test".to_string(),
+            etag: None,
+            uids: uids.iter().map(ToString::to_string).collect(),
+            ics: String::new(),
+        }
+    }
+
+    fn event_from(href: Option<&str>, uid: &str) -> CalendarEvent {
+        let start = Utc
+            .with_ymd_and_hms(2026, 1, 5, 10, 0, 0)
+            .single()
+            .expect("valid datetime");
+        CalendarEvent {
+            href: href.map(String::from),
+            ..recurring_test_event(uid, start, None)
+        }
+    }
+
+    fn event_uids(data: &CalendarData) -> Vec<&str> {
+        data.events.iter().map(|e| e.uid.as_str()).collect()
+    }
+
+    #[test]
+    fn test_remove_resource_matches_href() {
+        let mut data = CalendarData::new();
+        data.events = vec![
+            event_from(Some("/test/ABC-123.ics"), "meeting"),
+            event_from(Some("/test/1.ics"), "1"),
+            event_from(Some("/test/11.ics"), "11"),
+        ];
+        data.objects.insert(
+            "/test/ABC-123.ics".to_string(),
+            test_object("/test/ABC-123.ics", &["meeting"]),
+        );
+
+        // The file name doesn't have to match the UID
+        assert_eq!(data.remove_resource("/test/ABC-123.ics"), 1);
+        assert!(data.objects.is_empty());
+
+        // Only the exact resource is removed, not every UID it ends with
+        assert_eq!(data.remove_resource("/test/11.ics"), 1);
+        assert_eq!(event_uids(&data), vec!["1"]);
+
+        assert_eq!(data.remove_resource("/test/unknown.ics"), 0);
+    }
+
+    #[test]
+    fn test_remove_resource_legacy_items_without_href() {
+        let mut data = CalendarData::new();
+        data.events = vec![
+            event_from(None, "by-object"),
+            event_from(None, "by-name"),
+            event_from(None, "xby-name"),
+        ];
+        data.objects.insert(
+            "/test/random.ics".to_string(),
+            test_object("/test/random.ics", &["by-object"]),
+        );
+
+        assert_eq!(data.remove_resource("/test/random.ics"), 1);
+        assert_eq!(data.remove_resource("/test/by-name.ics"), 1);
+        assert_eq!(event_uids(&data), vec!["xby-name"]);
+    }
+
+    #[test]
+    fn test_retain_resources() {
+        let mut data = CalendarData::new();
+        data.events = vec![
+            event_from(Some("/test/listed.ics"), "listed"),
+            event_from(Some("/test/gone.ics"), "gone"),
+            event_from(None, "legacy"),
+            CalendarEvent {
+                calendar_url: "/other".to_string(),
+                ..event_from(Some("/other/gone.ics"), "other")
+            },
+        ];
+        data.objects.insert(
+            "/test/gone.ics".to_string(),
+            test_object("/test/gone.ics", &["gone"]),
+        );
+
+        let listed = std::collections::HashSet::from(["/test/listed.ics".to_string()]);
+        assert_eq!(data.retain_resources("/test", &listed), 2);
+
+        // Other calendars are left alone
+        assert_eq!(event_uids(&data), vec!["listed", "other"]);
+        assert!(data.objects.is_empty());
+    }
+
+    #[test]
+    fn test_replace_resource() {
+        let mut data = CalendarData::new();
+        data.events = vec![
+            event_from(Some("/test/series.ics"), "series"),
+            event_from(Some("/test/series.ics"), "old-uid"),
+            event_from(None, "legacy"),
+            event_from(Some("/other/series.ics"), "series"),
+        ];
+        data.objects.insert(
+            "/test/series.ics".to_string(),
+            test_object("/test/series.ics", &["series", "old-uid"]),
+        );
+
+        let mut updated = event_from(Some("/test/series.ics"), "series");
+        updated.summary = "Updated".to_string();
+        data.replace_resource(
+            test_object("/test/series.ics", &["series", "legacy"]),
+            vec![updated, event_from(Some("/test/series.ics"), "legacy")],
+            Vec::new(),
+            Vec::new(),
+        );
+
+        // The copy in another resource is left alone
+        assert_eq!(event_uids(&data), vec!["series", "series", "legacy"]);
+        assert_eq!(data.events[0].href.as_deref(), Some("/other/series.ics"));
+        assert_eq!(data.events[1].summary, "Updated");
+        assert_eq!(
+            data.objects["/test/series.ics"].uids,
+            vec!["series", "legacy"]
+        );
+    }
 }
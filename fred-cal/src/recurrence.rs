@@ -3,98 +3,207 @@
 // license that can be found in the LICENSE file or at
 // https://opensource.org/licenses/MIT.
 
-use crate::models::CalendarEvent;
-use chrono::{DateTime, Utc};
-use rrule::RRuleSet;
+use crate::models::{CalendarEvent, Todo, TodoStatus};
+use crate::timezone::{VTimezone, resolve_iana};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use rrule::{Frequency, RRule, RRuleSet, Unvalidated};
 
-/// Configuration for recurrence expansion
-#[derive(Debug, Clone)]
+/// Limits applied when expanding recurring series at query time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RecurrenceConfig {
-    /// How many days forward from now to expand recurring events
-    pub expand_forward_days: i64,
-    /// How many days backward from now to expand recurring events
-    pub expand_backward_days: i64,
+    /// How many days before now occurrences are generated (`None` = unbounded)
+    pub past_days: Option<u32>,
+
+    /// How many days after now occurrences are generated (`None` = unbounded)
+    pub future_days: Option<u32>,
+
+    /// Maximum number of occurrences generated per series and query
+    /// (`None` = unlimited)
+    pub max_instances: Option<usize>,
 }
 
 impl Default for RecurrenceConfig {
     fn default() -> Self {
         Self {
-            expand_forward_days: 730,  // 2 years
-            expand_backward_days: 365, // 1 year
+            past_days: None,
+            future_days: None,
+            max_instances: Some(DEFAULT_MAX_INSTANCES),
         }
     }
 }
 
-/// Expand a recurring event into individual occurrences
+/// Default cap on occurrences per series, enough for a daily series over
+/// more than two years
+pub const DEFAULT_MAX_INSTANCES: usize = 1000;
+
+impl RecurrenceConfig {
+    /// Restrict a query range to the expansion window around `now`
+    ///
+    /// Returns `None` when the range lies entirely outside the window.
+    #[must_use]
+    pub fn clamp_range(
+        &self,
+        range_start: DateTime<Utc>,
+        range_end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let window_start = self
+            .past_days
+            .and_then(|days| now.checked_sub_signed(chrono::Duration::days(i64::from(days))));
+        let window_end = self
+            .future_days
+            .and_then(|days| now.checked_add_signed(chrono::Duration::days(i64::from(days))));
+
+        let start = window_start.map_or(range_start, |window| range_start.max(window));
+        let end = window_end.map_or(range_end, |window| range_end.min(window));
+
+        (start < end).then_some((start, end))
+    }
+}
+
+/// Expand a recurring event into the occurrences overlapping a time range
 ///
-/// Takes an event with an RRULE and generates individual event instances
-/// for each occurrence within the expansion window.
+/// Takes an event with an RRULE (and/or RDATEs) and generates individual event
+/// instances for each occurrence that overlaps `[range_start, range_end)`.
+/// Overrides (events sharing the master's UID with a RECURRENCE-ID) replace the
+/// generated occurrence they refer to, and cancelled overrides remove it
+/// entirely.
 ///
 /// # Arguments
 ///
 /// * `event` - The master recurring event (with rrule field)
-/// * `config` - Configuration for expansion window
+/// * `overrides` - Overridden occurrences of the same series
+/// * `range_start` - Start of the requested range
+/// * `range_end` - End of the requested range
 ///
 /// # Returns
 ///
-/// A vector of event instances, one for each occurrence. If the event has no
-/// RRULE or if RRULE parsing fails, returns a vector containing only the
-/// original event.
+/// A vector of event instances, one for each occurrence in the range. If the
+/// event has no RRULE or if RRULE parsing fails, the original event is
+/// returned when it overlaps the range.
+#[must_use]
 pub fn expand_recurring_event(
     event: &CalendarEvent,
-    config: &RecurrenceConfig,
+    overrides: &[&CalendarEvent],
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
 ) -> Vec<CalendarEvent> {
-    // If no RRULE, return the event as-is
-    let Some(rrule_str) = &event.rrule else {
-        return vec![event.clone()];
+    expand_recurring_event_limited(event, overrides, range_start, range_end, None).0
+}
+
+/// Expand a recurring event, generating at most `max_instances` occurrences
+///
+/// Works like [`expand_recurring_event`]; the returned flag is `true` when
+/// the series had more occurrences in the range than the cap allowed.
+#[must_use]
+pub fn expand_recurring_event_limited(
+    event: &CalendarEvent,
+    overrides: &[&CalendarEvent],
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+    max_instances: Option<usize>,
+) -> (Vec<CalendarEvent>, bool) {
+    let (instances, truncated) = generate_occurrences(event, range_start, range_end, max_instances);
+
+    (
+        apply_overrides(instances, overrides, range_start, range_end),
+        truncated,
+    )
+}
+
+/// Check whether an event overlaps `[range_start, range_end)`
+///
+/// Zero-length events (a DATE-TIME DTSTART without DTEND or DURATION)
+/// overlap the range when they start inside it.
+pub(crate) fn overlaps(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> bool {
+    // Event overlaps with the range if:
+    // - Event starts before range ends AND
+    // - Event ends after range starts (or is an instant inside the range)
+    start < range_end && (end > range_start || (start == end && start >= range_start))
+}
+
+/// Generate the occurrences of an event overlapping the given range
+fn generate_occurrences(
+    event: &CalendarEvent,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+    limit: Option<usize>,
+) -> (Vec<CalendarEvent>, bool) {
+    let single = || {
+        if overlaps(event.start, event.end, range_start, range_end) {
+            vec![event.clone()]
+        } else {
+            Vec::new()
+        }
     };
 
-    // Parse the RRULE
-    let rrule_result = parse_rrule(rrule_str, event.start);
-    let rrule_set = match rrule_result {
+    // If neither RRULE nor RDATE, the event is its only occurrence
+    if event.rrule.is_none() && event.rdates.is_empty() {
+        return (single(), false);
+    }
+
+    // Build the recurrence set, skipping occurrences that end long before
+    // the range
+    let duration = event.end - event.start;
+    let from = range_start
+        .checked_sub_signed(duration)
+        .unwrap_or(range_start);
+    let rrule_set = match build_rrule_set(event, from) {
         Ok(set) => set,
         Err(e) => {
             warn!(
                 "Failed to parse RRULE for event '{}': {}. Using original event only.",
                 event.summary, e
             );
-            return vec![event.clone()];
+            return (single(), false);
         }
     };
 
-    // Calculate expansion window
-    let now = Utc::now();
-    let window_start = now - chrono::Duration::days(config.expand_backward_days);
-    let window_end = now + chrono::Duration::days(config.expand_forward_days);
-
     debug!(
         "Expanding recurring event '{}' from {} to {}",
-        event.summary, window_start, window_end
+        event.summary, range_start, range_end
     );
 
-    // Generate occurrences within the window
-    // We need to limit the iteration to avoid generating thousands of occurrences
-    // for long-running recurring events
-    let duration = event.end - event.start;
-    let mut instances = Vec::new();
+    // Generate occurrences overlapping the range
+    let mut instances: Vec<CalendarEvent> = Vec::new();
+    let mut truncated = false;
 
     for occurrence in &rrule_set {
         // Convert to UTC DateTime
         // The rrule crate returns DateTime<rrule::Tz>, we need DateTime<Utc>
-        let occurrence_start = occurrence.with_timezone(&Utc);
+        let occurrence_start = from_series_time(event, &occurrence);
 
-        // Stop if we're past the window end
-        if occurrence_start > window_end {
+        // Occurrences are generated in order, so stop once past the range end
+        if occurrence_start >= range_end {
             break;
         }
 
-        // Skip if before window start
-        if occurrence_start < window_start {
+        // Create event instance for this occurrence
+        let occurrence_end = occurrence_start + duration;
+
+        // Skip occurrences that end before the range starts
+        if !overlaps(occurrence_start, occurrence_end, range_start, range_end) {
+            continue;
+        }
+
+        // An RDATE may coincide with a generated occurrence
+        if instances
+            .last()
+            .is_some_and(|last| last.start == occurrence_start)
+        {
             continue;
         }
 
-        // Skip if this occurrence is in the EXDATE list
-        if is_excluded_by_exdate(&occurrence_start, &event.exdates) {
+        // Skip if this occurrence is in the EXDATE list; date-only EXDATEs
+        // match by the occurrence's date in the series' zone
+        if is_excluded_by_exdate(&occurrence_start, &event.exdates)
+            || event.exdate_dates.contains(&occurrence.date_naive())
+        {
             debug!(
                 "Skipping occurrence at {} due to EXDATE for event '{}'",
                 occurrence_start, event.summary
@@ -102,32 +211,249 @@ pub fn expand_recurring_event(
             continue;
         }
 
-        // Create event instance for this occurrence
-        let occurrence_end = occurrence_start + duration;
+        if limit.is_some_and(|limit| instances.len() >= limit) {
+            warn!(
+                "Series '{}' has more than {} occurrences in range, truncating",
+                event.summary,
+                instances.len()
+            );
+            truncated = true;
+            break;
+        }
 
         let mut instance = event.clone();
         instance.start = occurrence_start;
         instance.end = occurrence_end;
+        if event.all_day {
+            instance.start_date = Some(occurrence_start.date_naive());
+            instance.end_date = Some(occurrence_end.date_naive());
+        }
+
+        // Identify the occurrence by its original start within the series
+        instance.recurrence_id = Some(occurrence_start);
+        instance.instance_id = CalendarEvent::make_instance_id(&event.uid, Some(occurrence_start));
+        instance.series_uid = Some(event.uid.clone());
+        instance.resolve_alarms();
 
         // Keep the RRULE in the instance so we know it's part of a recurring series
-        // but we could also clear it if we want each instance to be independent
 
         instances.push(instance);
     }
 
-    // If no instances were generated in the window, include the original
-    // This handles edge cases where the RRULE might have already ended
-    if instances.is_empty() {
-        debug!(
-            "No occurrences in window for '{}', including original event",
-            event.summary
-        );
-        instances.push(event.clone());
+    (instances, truncated)
+}
+
+/// Replace generated occurrences with their overrides
+///
+/// Every occurrence whose start matches an override's RECURRENCE-ID is removed.
+/// The override itself is added back unless it is cancelled or falls outside
+/// the requested range.
+fn apply_overrides(
+    mut instances: Vec<CalendarEvent>,
+    overrides: &[&CalendarEvent],
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Vec<CalendarEvent> {
+    if overrides.is_empty() {
+        return instances;
     }
 
+    instances.retain(|instance| {
+        !overrides.iter().any(|o| {
+            o.recurrence_id
+                .is_some_and(|rid| rid.timestamp() == instance.start.timestamp())
+        })
+    });
+
+    for override_event in overrides {
+        if override_event.is_cancelled() {
+            debug!(
+                "Dropping cancelled occurrence {:?} of '{}'",
+                override_event.recurrence_id, override_event.summary
+            );
+            continue;
+        }
+
+        if overlaps(
+            override_event.start,
+            override_event.end,
+            range_start,
+            range_end,
+        ) {
+            instances.push((*override_event).clone());
+        }
+    }
+
+    instances.sort_by_key(|instance| instance.start);
     instances
 }
 
+/// Expand a recurring todo into its pending occurrences due within a time range
+///
+/// Occurrences are anchored on the todo's DUE date (or DTSTART when there is
+/// no DUE). An occurrence is skipped when an override (RECURRENCE-ID) marks it
+/// completed or cancelled, or when the master itself has been completed up to
+/// that point, which is how Apple Reminders and Tasks.org roll a series
+/// forward. Pending overrides replace the occurrence they refer to.
+#[must_use]
+pub fn expand_recurring_todo(
+    todo: &Todo,
+    overrides: &[&Todo],
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Vec<Todo> {
+    pending_todo_occurrences(todo, overrides, range_start, range_end, None)
+}
+
+/// Expand a recurring todo, returning at most `max_instances` occurrences
+///
+/// Works like [`expand_recurring_todo`]; the returned flag is `true` when
+/// the series had more pending occurrences in the range than the cap allowed.
+#[must_use]
+pub fn expand_recurring_todo_limited(
+    todo: &Todo,
+    overrides: &[&Todo],
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+    max_instances: Option<usize>,
+) -> (Vec<Todo>, bool) {
+    let Some(max_instances) = max_instances else {
+        return (
+            pending_todo_occurrences(todo, overrides, range_start, range_end, None),
+            false,
+        );
+    };
+
+    // Ask for one more than allowed to find out whether the cap was hit
+    let mut occurrences = pending_todo_occurrences(
+        todo,
+        overrides,
+        range_start,
+        range_end,
+        Some(max_instances.saturating_add(1)),
+    );
+    let truncated = occurrences.len() > max_instances;
+    occurrences.truncate(max_instances);
+
+    (occurrences, truncated)
+}
+
+/// Find the next pending occurrence of a recurring todo
+///
+/// Returns `None` when every occurrence has been completed or the series
+/// has been cancelled.
+#[must_use]
+pub fn next_pending_todo(todo: &Todo, overrides: &[&Todo]) -> Option<Todo> {
+    pending_todo_occurrences(
+        todo,
+        overrides,
+        DateTime::<Utc>::MIN_UTC,
+        DateTime::<Utc>::MAX_UTC,
+        Some(1),
+    )
+    .pop()
+}
+
+/// Generate pending occurrences of a recurring todo anchored in `[range_start, range_end)`
+fn pending_todo_occurrences(
+    todo: &Todo,
+    overrides: &[&Todo],
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+    limit: Option<usize>,
+) -> Vec<Todo> {
+    // Without an RRULE or a date to anchor it, the todo is its only occurrence
+    let (Some(rrule_str), Some(anchor)) = (&todo.rrule, todo.due.or(todo.start)) else {
+        return vec![todo.clone()];
+    };
+
+    if todo.is_cancelled() {
+        return Vec::new();
+    }
+
+    // Overrides may move an earlier occurrence into the range
+    let from = overrides
+        .iter()
+        .filter_map(|o| o.recurrence_id)
+        .fold(range_start, DateTime::min);
+    // Repeat in the todo's own zone, keeping its wall-clock time across DST
+    let zone = todo_expansion_zone(todo);
+    let rrule_set = match parse_rrule_from(rrule_str, anchor.with_timezone(&zone), Some(from)) {
+        Ok(set) => set,
+        Err(e) => {
+            warn!(
+                "Failed to parse RRULE for todo '{}': {}. Using original todo only.",
+                todo.summary, e
+            );
+            return vec![todo.clone()];
+        }
+    };
+
+    // A completed master means every occurrence up to the completion (or at
+    // least the current one) is done
+    let completed_through = todo.is_completed().then(|| {
+        todo.completed
+            .map_or(anchor, |completed| completed.max(anchor))
+    });
+
+    let mut occurrences = Vec::new();
+
+    for occurrence in &rrule_set {
+        let occurrence = occurrence.with_timezone(&Utc);
+
+        if occurrence >= range_end || limit.is_some_and(|limit| occurrences.len() >= limit) {
+            break;
+        }
+
+        if let Some(override_todo) = overrides.iter().find(|o| {
+            o.recurrence_id
+                .is_some_and(|rid| rid.timestamp() == occurrence.timestamp())
+        }) {
+            if !override_todo.is_completed()
+                && !override_todo.is_cancelled()
+                && override_todo
+                    .due
+                    .or(override_todo.start)
+                    .unwrap_or(occurrence)
+                    >= range_start
+            {
+                occurrences.push((*override_todo).clone());
+            }
+            continue;
+        }
+
+        if completed_through.is_some_and(|through| occurrence <= through)
+            || occurrence < range_start
+        {
+            continue;
+        }
+
+        let offset = occurrence - anchor;
+        let mut instance = todo.clone();
+        instance.due = todo.due.map(|due| due + offset);
+        instance.start = todo.start.map(|start| start + offset);
+        if todo.due_date.is_some() {
+            instance.due_date = instance.due.map(|due| due.date_naive());
+        }
+        if todo.start_date.is_some() {
+            instance.start_date = instance.start.map(|start| start.date_naive());
+        }
+        instance.recurrence_id = Some(occurrence);
+        instance.resolve_alarms();
+
+        // Occurrences after a completed one start over
+        if todo.is_completed() {
+            instance.status = TodoStatus::NeedsAction;
+            instance.completed = None;
+            instance.percent_complete = None;
+        }
+
+        occurrences.push(instance);
+    }
+
+    occurrences
+}
+
 /// Check if a datetime is excluded by EXDATE
 ///
 /// # Arguments
@@ -145,42 +471,197 @@ fn is_excluded_by_exdate(occurrence: &DateTime<Utc>, exdates: &[DateTime<Utc>])
     })
 }
 
+/// Build the `RRuleSet` for an event from its RRULE and RDATE values
+///
+/// The set is anchored in the event's own zone so that occurrences keep their
+/// wall-clock time across DST transitions. An event with RDATEs but no RRULE
+/// still has its DTSTART as the first instance, so it is added explicitly.
+fn build_rrule_set(event: &CalendarEvent, from: DateTime<Utc>) -> Result<RRuleSet, String> {
+    let dtstart = to_series_time(event, event.start);
+    let from = to_series_time(event, from).with_timezone(&Utc);
+
+    let mut rrule_set = match &event.rrule {
+        Some(rrule_str) => parse_rrule_from(rrule_str, dtstart, Some(from))?,
+        None => RRuleSet::new(dtstart).rdate(dtstart),
+    };
+
+    for rdate in &event.rdates {
+        rrule_set = rrule_set.rdate(to_series_time(event, *rdate));
+    }
+
+    Ok(rrule_set)
+}
+
+/// The VTIMEZONE a series has to be expanded in, if no IANA zone fits
+const fn custom_zone(event: &CalendarEvent) -> Option<&VTimezone> {
+    if event.all_day {
+        return None;
+    }
+
+    event.vtimezone.as_ref()
+}
+
+/// Convert a time to the zone a series is expanded in
+///
+/// Series in a zone only their calendar defines are expanded in wall-clock
+/// time, which the rrule crate holds as UTC.
+fn to_series_time(event: &CalendarEvent, time: DateTime<Utc>) -> DateTime<rrule::Tz> {
+    custom_zone(event)
+        .and_then(|zone| zone.to_local(&time))
+        .map_or_else(
+            || time.with_timezone(&expansion_zone(event)),
+            |local| rrule::Tz::UTC.from_utc_datetime(&local),
+        )
+}
+
+/// Convert an occurrence in the zone of [`to_series_time`] back to UTC
+fn from_series_time(event: &CalendarEvent, occurrence: &DateTime<rrule::Tz>) -> DateTime<Utc> {
+    custom_zone(event)
+        .and_then(|zone| zone.to_utc(&occurrence.naive_utc()))
+        .unwrap_or_else(|| occurrence.with_timezone(&Utc))
+}
+
+/// Determine the zone a series has to be expanded in
+///
+/// Events whose TZID resolves to an IANA zone (including Windows names) are
+/// expanded in that zone and floating events in the local zone; events in a
+/// zone defined by a VTIMEZONE are expanded in wall-clock time instead (see
+/// [`to_series_time`]). All-day
+/// events are stored as midnight UTC, so expanding them in UTC keeps their
+/// calendar date.
+fn expansion_zone(event: &CalendarEvent) -> rrule::Tz {
+    zone_of(event.all_day, event.tzid.as_deref(), event.floating)
+}
+
+/// Determine the zone a recurring todo has to be expanded in
+///
+/// Like [`expansion_zone`], for the time the todo is anchored on (DUE, or
+/// DTSTART without a DUE).
+fn todo_expansion_zone(todo: &Todo) -> rrule::Tz {
+    let date_only = if todo.due.is_some() {
+        todo.due_date.is_some()
+    } else {
+        todo.start_date.is_some()
+    };
+    zone_of(date_only, todo.tzid.as_deref(), todo.floating)
+}
+
+/// The zone a series anchored on a date or a time in `tzid` repeats in
+fn zone_of(date_only: bool, tzid: Option<&str>, floating: bool) -> rrule::Tz {
+    if date_only {
+        return rrule::Tz::UTC;
+    }
+
+    if let Some(tz) = tzid.and_then(resolve_iana) {
+        return rrule::Tz::Tz(tz);
+    }
+
+    if floating {
+        return rrule::Tz::LOCAL;
+    }
+
+    rrule::Tz::UTC
+}
+
 /// Parse an RRULE string into an `RRuleSet`
 ///
 /// # Arguments
 ///
 /// * `rrule_str` - The RRULE string (e.g., "FREQ=WEEKLY;INTERVAL=2")
-/// * `dtstart` - The start datetime of the original event
+/// * `dtstart` - The start datetime of the original event, in the series' zone
 ///
 /// # Returns
 ///
 /// An `RRuleSet` that can generate occurrences
-fn parse_rrule(rrule_str: &str, dtstart: DateTime<Utc>) -> Result<RRuleSet, String> {
+pub(crate) fn parse_rrule(
+    rrule_str: &str,
+    dtstart: DateTime<rrule::Tz>,
+) -> Result<RRuleSet, String> {
+    parse_rrule_from(rrule_str, dtstart, None)
+}
+
+/// Parse an RRULE string, leaving out occurrences well before `from`
+///
+/// Iterating a rule walks every occurrence from DTSTART, so a dense series
+/// that started years ago is started closer to `from` instead (see
+/// [`skip_ahead`]). Occurrences at or after `from` are unaffected.
+fn parse_rrule_from(
+    rrule_str: &str,
+    dtstart: DateTime<rrule::Tz>,
+    from: Option<DateTime<Utc>>,
+) -> Result<RRuleSet, String> {
     // Normalize UNTIL dates to UTC format
     // Some calendars provide UNTIL in local/floating format (YYYYMMDD or YYYYMMDDTHHMMSS)
-    // but the rrule crate requires UNTIL to match DTSTART timezone (UTC in our case)
-    let normalized_rrule = normalize_until_to_utc(rrule_str);
-
-    // Build the complete RRULE with DTSTART
-    // The rrule crate expects a full iCalendar RRULE format
-    let rrule_with_dtstart = format!(
-        "DTSTART:{}\nRRULE:{}",
-        dtstart.format("%Y%m%dT%H%M%SZ"),
-        normalized_rrule
+    // but the rrule crate requires UNTIL in UTC when DTSTART carries a timezone
+    let normalized_rrule = normalize_until_to_utc(rrule_str, dtstart.timezone());
+
+    debug!(
+        "Parsing RRULE: {} (DTSTART {} {})",
+        normalized_rrule,
+        dtstart.naive_local(),
+        dtstart.timezone().name()
     );
 
-    debug!("Parsing RRULE: {}", rrule_with_dtstart);
+    let rrule = normalized_rrule
+        .parse::<RRule<Unvalidated>>()
+        .map_err(|e| format!("RRULE parse error: {e}"))?;
+    let dtstart = from.map_or(dtstart, |from| skip_ahead(&rrule, dtstart, from));
+    let rrule = rrule
+        .validate(dtstart)
+        .map_err(|e| format!("RRULE parse error: {e}"))?;
+
+    Ok(RRuleSet::new(dtstart).rrule(rrule))
+}
+
+/// Move a series' DTSTART forward by whole periods to shortly before `from`
+///
+/// Rules stepping by days or weeks generate the same occurrences from any
+/// later start that lies on their grid, keeping the time of day and
+/// weekday. Rules with COUNT are counted from the original DTSTART, and
+/// monthly and yearly rules are sparse enough to walk, so those keep it.
+fn skip_ahead(
+    rrule: &RRule<Unvalidated>,
+    dtstart: DateTime<rrule::Tz>,
+    from: DateTime<Utc>,
+) -> DateTime<rrule::Tz> {
+    let period_days = match rrule.get_freq() {
+        Frequency::Yearly | Frequency::Monthly => return dtstart,
+        Frequency::Weekly => 7,
+        Frequency::Daily | Frequency::Hourly | Frequency::Minutely | Frequency::Secondly => 1,
+    } * i64::from(rrule.get_interval().max(1));
+    if rrule.get_count().is_some() {
+        return dtstart;
+    }
+
+    // A start after UNTIL fails validation
+    let target = rrule
+        .get_until()
+        .map_or(from, |until| from.min(until.with_timezone(&Utc)));
+    if target <= dtstart.with_timezone(&Utc) {
+        return dtstart;
+    }
+
+    // Stay a whole period short of the target, whatever the UTC offset
+    let zone = dtstart.timezone();
+    let elapsed_days =
+        (target.with_timezone(&zone).naive_local() - dtstart.naive_local()).num_days();
+    let periods = elapsed_days / period_days - 1;
+    if periods <= 0 {
+        return dtstart;
+    }
 
-    // Parse the RRULE
-    rrule_with_dtstart
-        .parse::<RRuleSet>()
-        .map_err(|e| format!("RRULE parse error: {e}"))
+    dtstart
+        .naive_local()
+        .checked_add_signed(chrono::Duration::days(periods * period_days))
+        .and_then(|local| zone.from_local_datetime(&local).earliest())
+        .unwrap_or(dtstart)
 }
 
 /// Normalize UNTIL dates in RRULE to UTC format
 ///
-/// Converts UNTIL=YYYYMMDD or UNTIL=YYYYMMDDTHHMMSS to UNTIL=YYYYMMDDTHHMMSSZ
-fn normalize_until_to_utc(rrule_str: &str) -> String {
+/// Converts UNTIL=YYYYMMDD or UNTIL=YYYYMMDDTHHMMSS to UNTIL=YYYYMMDDTHHMMSSZ,
+/// interpreting the local value in the series' zone.
+fn normalize_until_to_utc(rrule_str: &str, zone: rrule::Tz) -> String {
     // Look for UNTIL= parameter
     if !rrule_str.contains("UNTIL=") {
         return rrule_str.to_string();
@@ -200,13 +681,15 @@ fn normalize_until_to_utc(rrule_str: &str) -> String {
                     } else if until_value.len() == 8
                         && until_value.chars().all(|c| c.is_ascii_digit())
                     {
-                        // If it's just a date (8 digits), convert to datetime at midnight UTC
-                        format!("UNTIL={until_value}T000000Z")
+                        // If it's just a date (8 digits), convert to datetime at midnight
+                        localize_until(&format!("{until_value}T000000"), zone)
+                            .map_or_else(|| part.to_string(), |utc| format!("UNTIL={utc}"))
                     } else if until_value.len() == 15
                         && until_value.chars().all(|c| c.is_ascii_digit() || c == 'T')
                     {
-                        // If it's a datetime without Z, add Z
-                        format!("UNTIL={until_value}Z")
+                        // If it's a datetime without Z, convert it to UTC
+                        localize_until(until_value, zone)
+                            .map_or_else(|| part.to_string(), |utc| format!("UNTIL={utc}"))
                     } else {
                         // Otherwise return as-is
                         part.to_string()
@@ -219,12 +702,33 @@ fn normalize_until_to_utc(rrule_str: &str) -> String {
     normalized_parts.join(";")
 }
 
+/// Convert a local `YYYYMMDDTHHMMSS` value in `zone` to a UTC `YYYYMMDDTHHMMSSZ` value
+fn localize_until(value: &str, zone: rrule::Tz) -> Option<String> {
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let local = zone.from_local_datetime(&naive).earliest()?;
+    Some(
+        local
+            .with_timezone(&Utc)
+            .format("%Y%m%dT%H%M%SZ")
+            .to_string(),
+    )
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
+    use crate::models::EventStatus;
     use chrono::TimeZone;
 
+    fn window(backward_days: i64, forward_days: i64) -> (DateTime<Utc>, DateTime<Utc>) {
+        let now = Utc::now();
+        (
+            now - chrono::Duration::days(backward_days),
+            now + chrono::Duration::days(forward_days),
+        )
+    }
+
     fn create_test_event(
         summary: &str,
         start: DateTime<Utc>,
@@ -246,6 +750,31 @@ mod tests {
             exdates: Vec::new(),
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         }
     }
 
@@ -271,6 +800,31 @@ mod tests {
             exdates,
             status: None,
             etag: None,
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         }
     }
 
@@ -280,8 +834,8 @@ mod tests {
         let end = Utc.with_ymd_and_hms(2026, 1, 1, 11, 0, 0).unwrap();
         let event = create_test_event("Test", start, end, None);
 
-        let config = RecurrenceConfig::default();
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(365, 730);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         assert_eq!(instances.len(), 1);
         assert_eq!(instances[0].start, start);
@@ -295,11 +849,8 @@ mod tests {
 
         let event = create_test_event("Daily", start, end, Some("FREQ=DAILY;COUNT=5".to_string()));
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should get 5 instances (COUNT=5)
         assert_eq!(instances.len(), 5);
@@ -324,11 +875,8 @@ mod tests {
             Some("FREQ=WEEKLY;COUNT=4".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 60,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 60);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should get 4 instances
         assert_eq!(instances.len(), 4);
@@ -354,11 +902,8 @@ mod tests {
             Some("FREQ=WEEKLY;INTERVAL=2;COUNT=3".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 60,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 60);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should get 3 instances
         assert_eq!(instances.len(), 3);
@@ -377,8 +922,8 @@ mod tests {
 
         let event = create_test_event("Invalid", start, end, Some("INVALID_RRULE".to_string()));
 
-        let config = RecurrenceConfig::default();
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(365, 730);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should fall back to original event
         assert_eq!(instances.len(), 1);
@@ -400,11 +945,8 @@ mod tests {
             Some("FREQ=DAILY;COUNT=100".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 30,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(30, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should only get instances within the 60-day window around now
         for instance in &instances {
@@ -423,6 +965,68 @@ mod tests {
         assert!(instances.len() < 100);
     }
 
+    #[test]
+    fn test_expansion_skips_ahead_to_range() {
+        let dtstart = Utc.with_ymd_and_hms(2025, 1, 2, 9, 3, 0).unwrap();
+        let duration = chrono::Duration::minutes(30);
+        // Covers the start of daylight saving time in New York
+        let range_start = Utc.with_ymd_and_hms(2026, 3, 7, 10, 0, 0).unwrap();
+        let range_end = range_start + chrono::Duration::days(3);
+
+        for rule in [
+            "FREQ=MINUTELY;INTERVAL=7;BYHOUR=9,10",
+            "FREQ=HOURLY;INTERVAL=5",
+            "FREQ=DAILY;INTERVAL=3",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=SA,MO",
+            "FREQ=DAILY;UNTIL=20260309T000000Z",
+        ] {
+            for tzid in [None, Some("America/New_York")] {
+                let mut event =
+                    create_test_event("Skip", dtstart, dtstart + duration, Some(rule.into()));
+                event.tzid = tzid.map(str::to_string);
+
+                // Walk the whole series from DTSTART
+                let zone = expansion_zone(&event);
+                let expected: Vec<DateTime<Utc>> = parse_rrule(rule, dtstart.with_timezone(&zone))
+                    .unwrap()
+                    .into_iter()
+                    .map(|occurrence| occurrence.with_timezone(&Utc))
+                    .skip_while(|start| *start + duration <= range_start)
+                    .take_while(|start| *start < range_end)
+                    .collect();
+                let starts: Vec<DateTime<Utc>> =
+                    expand_recurring_event(&event, &[], range_start, range_end)
+                        .iter()
+                        .map(|instance| instance.start)
+                        .collect();
+
+                assert!(!expected.is_empty(), "{rule}");
+                assert_eq!(starts, expected, "{rule} in {tzid:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_skip_ahead_keeps_dtstart_with_count() {
+        let dtstart = Utc
+            .with_ymd_and_hms(2016, 1, 1, 0, 0, 0)
+            .unwrap()
+            .with_timezone(&rrule::Tz::UTC);
+        let from = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let rule = |s: &str| s.parse::<RRule<Unvalidated>>().unwrap();
+
+        assert_eq!(
+            skip_ahead(&rule("FREQ=MINUTELY;COUNT=5"), dtstart, from),
+            dtstart
+        );
+        assert_eq!(skip_ahead(&rule("FREQ=MONTHLY"), dtstart, from), dtstart);
+
+        let skipped = skip_ahead(&rule("FREQ=MINUTELY"), dtstart, from);
+        assert!(skipped > dtstart);
+        assert!(skipped.with_timezone(&Utc) < from);
+        assert!(from - skipped.with_timezone(&Utc) <= chrono::Duration::days(2));
+    }
+
     #[test]
     fn test_event_duration_preserved() {
         let start = Utc::now().date_naive().and_hms_opt(10, 0, 0).unwrap();
@@ -436,11 +1040,8 @@ mod tests {
             Some("FREQ=DAILY;COUNT=3".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // All instances should have the same 2-hour duration
         for instance in &instances {
@@ -462,11 +1063,8 @@ mod tests {
             Some("FREQ=WEEKLY;INTERVAL=52".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 730,  // 2 years
-            expand_backward_days: 365, // 1 year
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(365, 730);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should get instances in the expansion window (not the 2014 original)
         // The event repeats every 52 weeks from 2014, so we should see 2025, 2026, 2027, 2028
@@ -509,22 +1107,22 @@ mod tests {
     fn test_normalize_until_to_utc() {
         // Test date-only UNTIL
         let rrule = "FREQ=WEEKLY;UNTIL=20140315;INTERVAL=52";
-        let normalized = normalize_until_to_utc(rrule);
+        let normalized = normalize_until_to_utc(rrule, rrule::Tz::UTC);
         assert_eq!(normalized, "FREQ=WEEKLY;UNTIL=20140315T000000Z;INTERVAL=52");
 
         // Test datetime UNTIL without Z
         let rrule = "FREQ=DAILY;UNTIL=20140315T120000;COUNT=10";
-        let normalized = normalize_until_to_utc(rrule);
+        let normalized = normalize_until_to_utc(rrule, rrule::Tz::UTC);
         assert_eq!(normalized, "FREQ=DAILY;UNTIL=20140315T120000Z;COUNT=10");
 
         // Test already has Z
         let rrule = "FREQ=DAILY;UNTIL=20140315T120000Z";
-        let normalized = normalize_until_to_utc(rrule);
+        let normalized = normalize_until_to_utc(rrule, rrule::Tz::UTC);
         assert_eq!(normalized, "FREQ=DAILY;UNTIL=20140315T120000Z");
 
         // Test no UNTIL
         let rrule = "FREQ=WEEKLY;INTERVAL=2";
-        let normalized = normalize_until_to_utc(rrule);
+        let normalized = normalize_until_to_utc(rrule, rrule::Tz::UTC);
         assert_eq!(normalized, "FREQ=WEEKLY;INTERVAL=2");
     }
 
@@ -542,47 +1140,24 @@ mod tests {
             Some("FREQ=DAILY;UNTIL=20140110".to_string()),
         );
 
-        let config = RecurrenceConfig::default();
-        let instances = expand_recurring_event(&event, &config);
+        let range_start = Utc.with_ymd_and_hms(2014, 1, 1, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2014, 2, 1, 0, 0, 0).unwrap();
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should successfully parse and expand (won't error out)
-        // The event is from 2014, so no instances in our window, but original included
-        assert_eq!(instances.len(), 1);
-    }
+        // UNTIL is midnight on Jan 10, so the last occurrence is Jan 9
+        assert_eq!(instances.len(), 9);
 
-    #[test]
-    fn test_recurrence_config_default() {
-        let config = RecurrenceConfig::default();
-        assert_eq!(config.expand_forward_days, 730);
-        assert_eq!(config.expand_backward_days, 365);
-    }
-
-    #[test]
-    fn test_recurrence_config_clone() {
-        let config = RecurrenceConfig {
-            expand_forward_days: 100,
-            expand_backward_days: 50,
-        };
-        #[allow(clippy::redundant_clone)]
-        let cloned = config.clone();
-        assert_eq!(cloned.expand_forward_days, 100);
-        assert_eq!(cloned.expand_backward_days, 50);
-    }
-
-    #[test]
-    fn test_recurrence_config_debug() {
-        let config = RecurrenceConfig::default();
-        let debug_str = format!("{config:?}");
-        assert!(debug_str.contains("RecurrenceConfig"));
-        assert!(debug_str.contains("730"));
-        assert!(debug_str.contains("365"));
+        // Nothing is generated for a range after the series ended
+        let (range_start, range_end) = window(365, 730);
+        assert!(expand_recurring_event(&event, &[], range_start, range_end).is_empty());
     }
 
     #[test]
     fn test_normalize_until_invalid_format() {
         // Test UNTIL with invalid format (not 8 or 15 chars, or has invalid chars)
         let rrule = "FREQ=DAILY;UNTIL=invalid;COUNT=5";
-        let normalized = normalize_until_to_utc(rrule);
+        let normalized = normalize_until_to_utc(rrule, rrule::Tz::UTC);
         // Should return as-is when format doesn't match expectations
         assert_eq!(normalized, "FREQ=DAILY;UNTIL=invalid;COUNT=5");
     }
@@ -591,7 +1166,7 @@ mod tests {
     fn test_normalize_until_with_multiple_params() {
         // Test RRULE with multiple parameters including UNTIL
         let rrule = "FREQ=WEEKLY;BYDAY=MO,WE,FR;UNTIL=20260315;INTERVAL=1";
-        let normalized = normalize_until_to_utc(rrule);
+        let normalized = normalize_until_to_utc(rrule, rrule::Tz::UTC);
         assert_eq!(
             normalized,
             "FREQ=WEEKLY;BYDAY=MO,WE,FR;UNTIL=20260315T000000Z;INTERVAL=1"
@@ -612,11 +1187,8 @@ mod tests {
         );
 
         // Small window
-        let config = RecurrenceConfig {
-            expand_forward_days: 5,
-            expand_backward_days: 2,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(2, 5);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should only get instances within 7-day window
         assert!(instances.len() <= 7);
@@ -635,15 +1207,11 @@ mod tests {
             Some("FREQ=DAILY;UNTIL=20000105".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 30,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(30, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
-        // No instances in window, should include original
-        assert_eq!(instances.len(), 1);
-        assert_eq!(instances[0].start, start);
+        // No instances in the requested range
+        assert!(instances.is_empty());
     }
 
     #[test]
@@ -659,11 +1227,8 @@ mod tests {
             Some("FREQ=MONTHLY;COUNT=3".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 120,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 120);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should get 3 instances
         assert_eq!(instances.len(), 3);
@@ -683,11 +1248,8 @@ mod tests {
             Some("FREQ=WEEKLY;BYDAY=MO,WE;COUNT=6".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 60,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 60);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should get up to 6 instances
         assert!(instances.len() <= 6);
@@ -713,12 +1275,37 @@ mod tests {
             all_day: false,
             rrule: Some("FREQ=DAILY;COUNT=2".to_string()),
             exdates: Vec::new(),
-            status: Some("CONFIRMED".to_string()),
+            status: Some(EventStatus::Confirmed),
             etag: Some("etag123".to_string()),
+            recurrence_id: None,
+            rdates: Vec::new(),
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            instance_id: String::new(),
+            series_uid: None,
+            start_date: None,
+            end_date: None,
+            exdate_dates: Vec::new(),
+            alarms: Vec::new(),
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
         };
 
-        let config = RecurrenceConfig::default();
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(365, 730);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Verify all fields are preserved in instances
         assert_eq!(instances.len(), 2);
@@ -727,7 +1314,7 @@ mod tests {
             assert_eq!(instance.description, Some("Description".to_string()));
             assert_eq!(instance.location, Some("Office".to_string()));
             assert_eq!(instance.calendar_color, Some("#0000FF".to_string()));
-            assert_eq!(instance.status, Some("CONFIRMED".to_string()));
+            assert_eq!(instance.status, Some(EventStatus::Confirmed));
             assert_eq!(instance.etag, Some("etag123".to_string()));
         }
     }
@@ -746,11 +1333,8 @@ mod tests {
             Some("FREQ=DAILY;COUNT=1000".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 10,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 10);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should stop at window end, not generate all 1000
         assert!(instances.len() < 1000);
@@ -761,7 +1345,7 @@ mod tests {
     fn test_normalize_until_already_normalized() {
         // Test that already normalized UNTIL stays the same
         let rrule = "FREQ=WEEKLY;UNTIL=20260315T120000Z;INTERVAL=2";
-        let normalized = normalize_until_to_utc(rrule);
+        let normalized = normalize_until_to_utc(rrule, rrule::Tz::UTC);
         assert_eq!(normalized, rrule);
     }
 
@@ -780,11 +1364,8 @@ mod tests {
             Some("FREQ=DAILY;COUNT=200".to_string()),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 30,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(30, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should skip instances before window start
         // and only include those within the window
@@ -816,11 +1397,8 @@ mod tests {
             exdates,
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should have 4 instances (5 - 1 excluded)
         assert_eq!(instances.len(), 4);
@@ -854,11 +1432,8 @@ mod tests {
             exdates.clone(),
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should have 5 instances (7 - 2 excluded)
         assert_eq!(instances.len(), 5);
@@ -892,11 +1467,8 @@ mod tests {
             exdates,
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 10,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 10);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
         // Should still have all instances in window since EXDATE is outside
         assert_eq!(instances.len(), 5);
@@ -923,15 +1495,11 @@ mod tests {
             exdates,
         );
 
-        let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 1,
-        };
-        let instances = expand_recurring_event(&event, &config);
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
 
-        // When all are excluded, should return original event as fallback
-        assert_eq!(instances.len(), 1);
-        assert_eq!(instances[0].start, start);
+        // When all are excluded, nothing is left to show
+        assert!(instances.is_empty());
     }
 
     #[test]
@@ -961,13 +1529,704 @@ mod tests {
             Vec::new(),
         );
 
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        // Should have all 3 instances
+        assert_eq!(instances.len(), 3);
+    }
+
+    #[test]
+    fn test_expand_with_moved_override() {
+        let start = Utc::now().date_naive().and_hms_opt(9, 0, 0).unwrap();
+        let start = Utc.from_utc_datetime(&start);
+        let end = start + chrono::Duration::minutes(15);
+
+        let event = create_test_event(
+            "Standup",
+            start,
+            end,
+            Some("FREQ=DAILY;COUNT=3".to_string()),
+        );
+
+        let original = start + chrono::Duration::days(1);
+        let mut moved = create_test_event(
+            "Standup",
+            original + chrono::Duration::hours(2),
+            original + chrono::Duration::hours(2) + chrono::Duration::minutes(15),
+            None,
+        );
+        moved.summary = "Standup (moved)".to_string();
+        moved.recurrence_id = Some(original);
+
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[&moved], range_start, range_end);
+
+        assert_eq!(instances.len(), 3);
+        assert!(instances.iter().all(|i| i.start != original));
+        assert_eq!(instances[1].summary, "Standup (moved)");
+        assert_eq!(instances[1].start, original + chrono::Duration::hours(2));
+    }
+
+    #[test]
+    fn test_expand_with_cancelled_override() {
+        let start = Utc::now().date_naive().and_hms_opt(9, 0, 0).unwrap();
+        let start = Utc.from_utc_datetime(&start);
+        let end = start + chrono::Duration::minutes(15);
+
+        let event = create_test_event(
+            "Standup",
+            start,
+            end,
+            Some("FREQ=DAILY;COUNT=3".to_string()),
+        );
+
+        let original = start + chrono::Duration::days(2);
+        let mut cancelled = create_test_event(
+            "Standup",
+            original,
+            original + chrono::Duration::minutes(15),
+            None,
+        );
+        cancelled.recurrence_id = Some(original);
+        cancelled.status = Some(EventStatus::Cancelled);
+
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[&cancelled], range_start, range_end);
+
+        assert_eq!(instances.len(), 2);
+        assert!(instances.iter().all(|i| i.start != original));
+    }
+
+    #[test]
+    fn test_expand_with_rdates() {
+        let start = Utc::now().date_naive().and_hms_opt(10, 0, 0).unwrap();
+        let start = Utc.from_utc_datetime(&start);
+        let end = start + chrono::Duration::hours(1);
+
+        let mut event = create_test_event(
+            "With RDATE",
+            start,
+            end,
+            Some("FREQ=WEEKLY;COUNT=2".to_string()),
+        );
+        let extra = start + chrono::Duration::days(3);
+        // Duplicates a generated occurrence and must not appear twice
+        let duplicate = start + chrono::Duration::days(7);
+        event.rdates = vec![extra, duplicate];
+
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        let starts: Vec<_> = instances.iter().map(|i| i.start).collect();
+        assert_eq!(starts, vec![start, extra, duplicate]);
+        assert_eq!(instances[1].end, extra + chrono::Duration::hours(1));
+    }
+
+    #[test]
+    fn test_expand_rdates_without_rrule() {
+        let start = Utc::now().date_naive().and_hms_opt(10, 0, 0).unwrap();
+        let start = Utc.from_utc_datetime(&start);
+        let end = start + chrono::Duration::hours(1);
+
+        let mut event = create_test_event("RDATE only", start, end, None);
+        event.rdates = vec![
+            start + chrono::Duration::days(2),
+            start + chrono::Duration::days(5),
+        ];
+
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        // DTSTART plus the two RDATEs
+        assert_eq!(instances.len(), 3);
+        assert_eq!(instances[0].start, start);
+        assert_eq!(instances[2].start, start + chrono::Duration::days(5));
+    }
+
+    #[test]
+    fn test_expand_rdate_excluded_by_exdate() {
+        let start = Utc::now().date_naive().and_hms_opt(10, 0, 0).unwrap();
+        let start = Utc.from_utc_datetime(&start);
+        let end = start + chrono::Duration::hours(1);
+
+        let rdate = start + chrono::Duration::days(2);
+        let mut event = create_test_event_with_exdates(
+            "RDATE with EXDATE",
+            start,
+            end,
+            Some("FREQ=WEEKLY;COUNT=2".to_string()),
+            vec![rdate],
+        );
+        event.rdates = vec![rdate];
+
+        let (range_start, range_end) = window(1, 30);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        assert_eq!(instances.len(), 2);
+        assert!(instances.iter().all(|i| i.start != rdate));
+    }
+
+    #[test]
+    fn test_expand_in_timezone_across_dst() {
+        // 9:00 America/Chicago is 15:00 UTC in winter (CST)
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 15, 0, 0).unwrap();
+        let end = start + chrono::Duration::hours(1);
+
+        let mut event = create_test_event(
+            "Chicago weekly",
+            start,
+            end,
+            Some("FREQ=WEEKLY;COUNT=3".to_string()),
+        );
+        event.tzid = Some("America/Chicago".to_string());
+
+        let (range_start, range_end) = window(3650, 3650);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        // DST starts on 2026-03-08, so later occurrences are at 14:00 UTC
+        let starts: Vec<_> = instances.iter().map(|i| i.start).collect();
+        assert_eq!(
+            starts,
+            vec![
+                start,
+                Utc.with_ymd_and_hms(2026, 3, 9, 14, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 16, 14, 0, 0).unwrap(),
+            ]
+        );
+        assert!(
+            instances
+                .iter()
+                .all(|i| i.end - i.start == chrono::Duration::hours(1))
+        );
+    }
+
+    #[test]
+    fn test_expand_todo_in_timezone_across_dst() {
+        // 9:00 America/Chicago is 15:00 UTC before DST (CST)
+        let due = Utc.with_ymd_and_hms(2026, 3, 2, 15, 0, 0).unwrap();
+        let mut todo = create_test_todo("Chicago weekly", due, Some("FREQ=WEEKLY;COUNT=3"));
+        todo.tzid = Some("America/Chicago".to_string());
+
+        let (range_start, range_end) = window(3650, 3650);
+        let occurrences = expand_recurring_todo(&todo, &[], range_start, range_end);
+
+        // DST starts on 2026-03-08, so later occurrences are due at 14:00 UTC
+        let dues: Vec<_> = occurrences.iter().filter_map(|o| o.due).collect();
+        assert_eq!(
+            dues,
+            vec![
+                due,
+                Utc.with_ymd_and_hms(2026, 3, 9, 14, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 16, 14, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_with_local_until_in_timezone() {
+        // 9:00 Europe/Berlin is 08:00 UTC in winter (CET)
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 8, 0, 0).unwrap();
+        let end = start + chrono::Duration::hours(1);
+
+        let mut event = create_test_event(
+            "Berlin daily",
+            start,
+            end,
+            Some("FREQ=DAILY;UNTIL=20260107T090000".to_string()),
+        );
+        event.tzid = Some("Europe/Berlin".to_string());
+
+        let (range_start, range_end) = window(3650, 3650);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        // UNTIL is local Berlin time, so the 2026-01-07 occurrence is included
+        assert_eq!(instances.len(), 3);
+    }
+
+    #[test]
+    fn test_expand_floating_keeps_wall_clock() {
+        let start = chrono::Local
+            .with_ymd_and_hms(2026, 3, 1, 9, 0, 0)
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = start + chrono::Duration::hours(1);
+
+        let mut event = create_test_event(
+            "Floating weekly",
+            start,
+            end,
+            Some("FREQ=WEEKLY;COUNT=8".to_string()),
+        );
+        event.floating = true;
+
+        let (range_start, range_end) = window(3650, 3650);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        assert_eq!(instances.len(), 8);
+        for instance in &instances {
+            let local = instance.start.with_timezone(&chrono::Local);
+            assert_eq!(
+                local.time(),
+                chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_all_day_keeps_date() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 5, 0, 0, 0).unwrap();
+        let end = start + chrono::Duration::days(1);
+
+        let mut event = create_test_event(
+            "All-day weekly",
+            start,
+            end,
+            Some("FREQ=WEEKLY;COUNT=4".to_string()),
+        );
+        event.all_day = true;
+
+        let (range_start, range_end) = window(3650, 3650);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        assert_eq!(instances.len(), 4);
+        for (i, instance) in instances.iter().enumerate() {
+            let expected = start + chrono::Duration::weeks(i64::try_from(i).unwrap());
+            assert_eq!(instance.start, expected);
+            assert_eq!(instance.end - instance.start, chrono::Duration::days(1));
+        }
+    }
+
+    #[test]
+    fn test_expand_all_day_with_date_exdate() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 5, 0, 0, 0).unwrap();
+        let end = start + chrono::Duration::days(1);
+
+        let mut event = create_test_event(
+            "All-day weekly",
+            start,
+            end,
+            Some("FREQ=WEEKLY;COUNT=3".to_string()),
+        );
+        event.all_day = true;
+        event.exdate_dates = vec![chrono::NaiveDate::from_ymd_opt(2026, 3, 12).unwrap()];
+
+        let (range_start, range_end) = window(3650, 3650);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        let starts: Vec<_> = instances.iter().map(|i| i.start).collect();
+        assert_eq!(starts, vec![start, start + chrono::Duration::weeks(2)]);
+    }
+
+    #[test]
+    fn test_expand_zoned_event_with_date_exdate() {
+        // 23:30 in Berlin is already the next day in UTC; the date-only
+        // EXDATE has to match the date in the series' own zone
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 22, 30, 0).unwrap();
+        let end = start + chrono::Duration::hours(1);
+
+        let mut event = create_test_event(
+            "Late night",
+            start,
+            end,
+            Some("FREQ=DAILY;COUNT=3".to_string()),
+        );
+        event.tzid = Some("Europe/Berlin".to_string());
+        event.exdate_dates = vec![chrono::NaiveDate::from_ymd_opt(2026, 1, 6).unwrap()];
+
+        let (range_start, range_end) = window(3650, 3650);
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        let starts: Vec<_> = instances.iter().map(|i| i.start).collect();
+        assert_eq!(starts, vec![start, start + chrono::Duration::days(2)]);
+    }
+
+    #[test]
+    fn test_normalize_until_in_timezone() {
+        let zone = rrule::Tz::Tz(chrono_tz::America::New_York);
+
+        let normalized = normalize_until_to_utc("FREQ=DAILY;UNTIL=20260115T120000", zone);
+        assert_eq!(normalized, "FREQ=DAILY;UNTIL=20260115T170000Z");
+
+        let normalized = normalize_until_to_utc("FREQ=DAILY;UNTIL=20260115", zone);
+        assert_eq!(normalized, "FREQ=DAILY;UNTIL=20260115T050000Z");
+    }
+
+    #[test]
+    fn test_expand_far_future_range() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 10, 0, 0).unwrap();
+        let end = start + chrono::Duration::hours(1);
+
+        let event = create_test_event(
+            "Weekly",
+            start,
+            end,
+            Some("FREQ=WEEKLY;BYDAY=MO".to_string()),
+        );
+
+        let range_start = Utc.with_ymd_and_hms(2029, 1, 1, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2029, 1, 8, 0, 0, 0).unwrap();
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        assert_eq!(instances.len(), 1);
+        assert_eq!(
+            instances[0].start,
+            Utc.with_ymd_and_hms(2029, 1, 1, 10, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_expand_includes_occurrence_overlapping_range_start() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 22, 0, 0).unwrap();
+        let end = start + chrono::Duration::hours(4);
+
+        let event = create_test_event(
+            "Night shift",
+            start,
+            end,
+            Some("FREQ=DAILY;COUNT=5".to_string()),
+        );
+
+        // Occurrence starting on Jan 6 at 22:00 runs into Jan 7
+        let range_start = Utc.with_ymd_and_hms(2026, 1, 7, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 1, 7, 12, 0, 0).unwrap();
+        let instances = expand_recurring_event(&event, &[], range_start, range_end);
+
+        assert_eq!(instances.len(), 1);
+        assert_eq!(
+            instances[0].start,
+            Utc.with_ymd_and_hms(2026, 1, 6, 22, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_expand_moved_override_into_range() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 10, 0, 0).unwrap();
+        let end = start + chrono::Duration::hours(1);
+
+        let event = create_test_event(
+            "Weekly",
+            start,
+            end,
+            Some("FREQ=WEEKLY;COUNT=4".to_string()),
+        );
+
+        // The Jan 12 occurrence was moved to Jan 20
+        let original = Utc.with_ymd_and_hms(2026, 1, 12, 10, 0, 0).unwrap();
+        let moved_start = Utc.with_ymd_and_hms(2026, 1, 20, 10, 0, 0).unwrap();
+        let mut moved = create_test_event(
+            "Weekly",
+            moved_start,
+            moved_start + chrono::Duration::hours(1),
+            None,
+        );
+        moved.recurrence_id = Some(original);
+
+        let range_start = Utc.with_ymd_and_hms(2026, 1, 20, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 1, 21, 0, 0, 0).unwrap();
+        let instances = expand_recurring_event(&event, &[&moved], range_start, range_end);
+
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].start, moved_start);
+
+        // The original slot no longer shows up
+        let range_start = Utc.with_ymd_and_hms(2026, 1, 12, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 1, 13, 0, 0, 0).unwrap();
+        assert!(expand_recurring_event(&event, &[&moved], range_start, range_end).is_empty());
+    }
+
+    #[test]
+    fn test_expand_sets_instance_identifiers() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 10, 0, 0).unwrap();
+        let end = start + chrono::Duration::hours(1);
+
+        let event = create_test_event("Daily", start, end, Some("FREQ=DAILY;COUNT=3".to_string()));
+
+        let original = start + chrono::Duration::days(1);
+        let mut moved = create_test_event(
+            "Daily",
+            original + chrono::Duration::hours(1),
+            original + chrono::Duration::hours(2),
+            None,
+        );
+        moved.recurrence_id = Some(original);
+        moved.instance_id = CalendarEvent::make_instance_id(&moved.uid, Some(original));
+        moved.series_uid = Some(moved.uid.clone());
+
+        let range_end = start + chrono::Duration::days(7);
+        let instances = expand_recurring_event(&event, &[&moved], start, range_end);
+
+        let ids: Vec<_> = instances.iter().map(|i| i.instance_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "test-daily:20260105T100000Z",
+                "test-daily:20260106T100000Z",
+                "test-daily:20260107T100000Z",
+            ]
+        );
+        assert!(
+            instances
+                .iter()
+                .all(|i| i.series_uid.as_deref() == Some("test-daily"))
+        );
+        assert_eq!(instances[0].recurrence_id, Some(start));
+        assert_eq!(instances[1].recurrence_id, Some(original));
+    }
+
+    #[test]
+    fn test_expand_single_event_keeps_identifiers() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 10, 0, 0).unwrap();
+        let end = start + chrono::Duration::hours(1);
+
+        let mut event = create_test_event("Single", start, end, None);
+        event.instance_id = event.uid.clone();
+
+        let instances =
+            expand_recurring_event(&event, &[], start, start + chrono::Duration::days(1));
+
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].instance_id, "test-single");
+        assert_eq!(instances[0].series_uid, None);
+        assert_eq!(instances[0].recurrence_id, None);
+    }
+
+    fn create_test_todo(summary: &str, due: DateTime<Utc>, rrule: Option<&str>) -> Todo {
+        Todo {
+            uid: format!("test-{}", summary.to_lowercase().replace(' ', "-")),
+            summary: summary.to_string(),
+            description: None,
+            due: Some(due),
+            start: None,
+            completed: None,
+            priority: None,
+            percent_complete: None,
+            status: TodoStatus::NeedsAction,
+            calendar_name: "Test Calendar".to_string(),
+            calendar_url: "/calendar/test".to_string(),
+            etag: None,
+            rrule: rrule.map(String::from),
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
+        }
+    }
+
+    #[test]
+    fn test_expand_recurring_todo_in_range() {
+        let due = Utc.with_ymd_and_hms(2026, 1, 1, 17, 0, 0).unwrap();
+        let todo = create_test_todo("Pay rent", due, Some("FREQ=MONTHLY"));
+
+        let range_start = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 5, 1, 0, 0, 0).unwrap();
+        let occurrences = expand_recurring_todo(&todo, &[], range_start, range_end);
+
+        let dues: Vec<_> = occurrences.iter().map(|t| t.due.unwrap()).collect();
+        assert_eq!(
+            dues,
+            vec![
+                Utc.with_ymd_and_hms(2026, 3, 1, 17, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 4, 1, 17, 0, 0).unwrap(),
+            ]
+        );
+        assert_eq!(occurrences[0].recurrence_id, occurrences[0].due);
+    }
+
+    #[test]
+    fn test_recurring_todo_completed_master_rolls_forward() {
+        let due = Utc.with_ymd_and_hms(2026, 1, 1, 17, 0, 0).unwrap();
+        let mut todo = create_test_todo("Pay rent", due, Some("FREQ=MONTHLY"));
+        todo.status = TodoStatus::Completed;
+        todo.completed = Some(Utc.with_ymd_and_hms(2026, 2, 3, 9, 0, 0).unwrap());
+        todo.percent_complete = Some(100);
+
+        let next = next_pending_todo(&todo, &[]).unwrap();
+        assert_eq!(
+            next.due,
+            Some(Utc.with_ymd_and_hms(2026, 3, 1, 17, 0, 0).unwrap())
+        );
+        assert_eq!(next.status, TodoStatus::NeedsAction);
+        assert_eq!(next.completed, None);
+        assert_eq!(next.percent_complete, None);
+    }
+
+    #[test]
+    fn test_recurring_todo_completed_early_rolls_past_current() {
+        let due = Utc.with_ymd_and_hms(2026, 1, 10, 17, 0, 0).unwrap();
+        let mut todo = create_test_todo("Water plants", due, Some("FREQ=WEEKLY"));
+        todo.status = TodoStatus::Completed;
+        todo.completed = Some(Utc.with_ymd_and_hms(2026, 1, 8, 9, 0, 0).unwrap());
+
+        let next = next_pending_todo(&todo, &[]).unwrap();
+        assert_eq!(
+            next.due,
+            Some(Utc.with_ymd_and_hms(2026, 1, 17, 17, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_recurring_todo_skips_completed_override() {
+        let due = Utc.with_ymd_and_hms(2026, 1, 1, 17, 0, 0).unwrap();
+        let todo = create_test_todo("Pay rent", due, Some("FREQ=MONTHLY;COUNT=3"));
+
+        let mut done = create_test_todo("Pay rent", due, None);
+        done.recurrence_id = Some(due);
+        done.status = TodoStatus::Completed;
+
+        let second = Utc.with_ymd_and_hms(2026, 2, 1, 17, 0, 0).unwrap();
+        let mut renamed = create_test_todo("Pay rent", second, None);
+        renamed.summary = "Pay rent (late)".to_string();
+        renamed.recurrence_id = Some(second);
+
+        let next = next_pending_todo(&todo, &[&done, &renamed]).unwrap();
+        assert_eq!(next.summary, "Pay rent (late)");
+
+        let all = expand_recurring_todo(
+            &todo,
+            &[&done, &renamed],
+            DateTime::<Utc>::MIN_UTC,
+            DateTime::<Utc>::MAX_UTC,
+        );
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_recurring_todo_cancelled_or_finished() {
+        let due = Utc.with_ymd_and_hms(2026, 1, 1, 17, 0, 0).unwrap();
+
+        let mut cancelled = create_test_todo("Cancelled", due, Some("FREQ=DAILY"));
+        cancelled.status = TodoStatus::Cancelled;
+        assert!(next_pending_todo(&cancelled, &[]).is_none());
+
+        let mut finished = create_test_todo("Finished", due, Some("FREQ=DAILY;COUNT=2"));
+        finished.status = TodoStatus::Completed;
+        finished.completed = Some(Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap());
+        assert!(next_pending_todo(&finished, &[]).is_none());
+    }
+
+    #[test]
+    fn test_overlaps_zero_length_event() {
+        let range_start = Utc.with_ymd_and_hms(2026, 3, 15, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 3, 16, 0, 0, 0).unwrap();
+
+        // Instants at the start of the range are included, at the end are not
+        assert!(overlaps(range_start, range_start, range_start, range_end));
+        assert!(!overlaps(range_end, range_end, range_start, range_end));
+        // Regular events ending exactly at the range start are excluded
+        let before = range_start - chrono::Duration::hours(1);
+        assert!(!overlaps(before, range_start, range_start, range_end));
+    }
+
+    #[test]
+    fn test_recurrence_config_clamp_range() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap();
+        let range_start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+
+        let unbounded = RecurrenceConfig::default();
+        assert_eq!(
+            unbounded.clamp_range(range_start, range_end, now),
+            Some((range_start, range_end))
+        );
+
         let config = RecurrenceConfig {
-            expand_forward_days: 30,
-            expand_backward_days: 1,
+            past_days: Some(7),
+            future_days: Some(30),
+            max_instances: None,
         };
-        let instances = expand_recurring_event(&event, &config);
+        assert_eq!(
+            config.clamp_range(range_start, range_end, now),
+            Some((
+                now - chrono::Duration::days(7),
+                now + chrono::Duration::days(30)
+            ))
+        );
+
+        // A range entirely beyond the window yields nothing
+        let far_start = Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap();
+        let far_end = Utc.with_ymd_and_hms(2027, 2, 1, 0, 0, 0).unwrap();
+        assert_eq!(config.clamp_range(far_start, far_end, now), None);
+    }
+
+    #[test]
+    fn test_expand_recurring_event_limited_truncates() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 15, 0, 0, 0).unwrap();
+        let end = start + chrono::Duration::seconds(30);
+        let event = create_test_event(
+            "Every minute",
+            start,
+            end,
+            Some("FREQ=MINUTELY".to_string()),
+        );
+        let range_end = start + chrono::Duration::days(1);
+
+        let (instances, truncated) =
+            expand_recurring_event_limited(&event, &[], start, range_end, Some(10));
+        assert_eq!(instances.len(), 10);
+        assert!(truncated);
+
+        let (instances, truncated) =
+            expand_recurring_event_limited(&event, &[], start, range_end, None);
+        assert_eq!(instances.len(), 24 * 60);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_expand_recurring_todo_limited_truncates() {
+        let due = Utc.with_ymd_and_hms(2026, 1, 1, 17, 0, 0).unwrap();
+        let todo = create_test_todo("Water plants", due, Some("FREQ=DAILY"));
+        let range_end = Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap();
+
+        let (occurrences, truncated) =
+            expand_recurring_todo_limited(&todo, &[], due, range_end, Some(5));
+        assert_eq!(occurrences.len(), 5);
+        assert!(truncated);
+
+        let (occurrences, truncated) =
+            expand_recurring_todo_limited(&todo, &[], due, range_end, Some(31));
+        assert_eq!(occurrences.len(), 31);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_expand_resolves_alarms_per_occurrence() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let mut event = create_test_event(
+            "Standup",
+            start,
+            start + chrono::Duration::minutes(15),
+            Some("FREQ=DAILY;COUNT=3".to_string()),
+        );
+        event.alarms.push(crate::models::Alarm {
+            action: "DISPLAY".to_string(),
+            description: None,
+            trigger_offset_seconds: Some(-600),
+            trigger_related: Some("START".to_string()),
+            trigger_at: None,
+        });
+
+        let instances =
+            expand_recurring_event(&event, &[], start, start + chrono::Duration::days(7));
 
-        // Should have all 3 instances
         assert_eq!(instances.len(), 3);
+        for instance in &instances {
+            assert_eq!(
+                instance.alarms[0].trigger_at,
+                Some(instance.start - chrono::Duration::minutes(10))
+            );
+        }
     }
 }
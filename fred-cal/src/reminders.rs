@@ -0,0 +1,997 @@
+// Copyright (C) 2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+use crate::cache::CacheManager;
+use crate::models::{Alarm, CalendarData, CalendarEvent, Todo};
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use chrono::{DateTime, Local, Utc};
+use fast_dav_rs::common::{HyperClient, build_hyper_client};
+use http_body_util::Full;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::RwLock;
+use tokio::time::{Duration, interval};
+
+/// How late a missed alarm is still delivered (e.g. after a restart)
+const MAX_LATENESS_MINUTES: i64 = 60;
+
+/// How long delivered reminders are remembered before being pruned
+const RETENTION_DAYS: i64 = 7;
+
+/// How long a single sink may take to accept a reminder
+const SINK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Snooze length used when the caller doesn't specify one
+pub const DEFAULT_SNOOZE_MINUTES: i64 = 10;
+
+/// Destination for fired reminders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReminderSink {
+    /// POST the reminder as JSON to an HTTP(S) URL
+    Webhook(String),
+
+    /// Run a shell command with the reminder as JSON on stdin
+    Command(String),
+
+    /// Write the reminder as a JSON line to a named pipe (FIFO)
+    Pipe(PathBuf),
+}
+
+impl FromStr for ReminderSink {
+    type Err = anyhow::Error;
+
+    /// Parse a sink given as `webhook:<url>`, `command:<command>` or `pipe:<path>`
+    fn from_str(value: &str) -> Result<Self> {
+        let (kind, target) = value.split_once(':').ok_or_else(|| {
+            anyhow::anyhow!("Reminder sink must be webhook:<url>, command:<cmd> or pipe:<path>")
+        })?;
+
+        if target.trim().is_empty() {
+            anyhow::bail!("Reminder sink '{kind}' is missing its target");
+        }
+
+        match kind.to_ascii_lowercase().as_str() {
+            "webhook" => {
+                if !target.starts_with("http://") && !target.starts_with("https://") {
+                    anyhow::bail!("Webhook URL must start with http:// or https://");
+                }
+                Ok(Self::Webhook(target.to_string()))
+            }
+            "command" => Ok(Self::Command(target.to_string())),
+            "pipe" => Ok(Self::Pipe(PathBuf::from(target))),
+            other => anyhow::bail!("Unknown reminder sink type: {other}"),
+        }
+    }
+}
+
+/// A fired alarm of one event or todo occurrence
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Reminder {
+    /// Stable identifier (occurrence, alarm index and trigger time)
+    pub id: String,
+
+    /// UID of the event or todo
+    pub uid: String,
+
+    /// Occurrence the alarm belongs to
+    pub instance_id: String,
+
+    /// Either "event" or "todo"
+    pub item_type: String,
+
+    /// Summary of the event or todo
+    pub summary: String,
+
+    /// Calendar the item belongs to
+    pub calendar_name: String,
+
+    /// Alarm action (DISPLAY, AUDIO, EMAIL)
+    pub action: String,
+
+    /// Alarm description, if any
+    pub description: Option<String>,
+
+    /// When the alarm was due to fire
+    pub trigger_at: DateTime<Utc>,
+
+    /// Start of the event or todo (if any)
+    pub start: Option<DateTime<Utc>>,
+
+    /// End of the event (events only)
+    pub end: Option<DateTime<Utc>>,
+
+    /// Due time of the todo (todos only)
+    pub due: Option<DateTime<Utc>>,
+}
+
+/// A reminder that has been delivered, with its acknowledgement state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveredReminder {
+    pub reminder: Reminder,
+
+    /// When the reminder was last delivered
+    pub fired_at: DateTime<Utc>,
+
+    /// Whether the reminder has been acknowledged
+    pub acknowledged: bool,
+
+    /// When a snoozed reminder is delivered again
+    pub snoozed_until: Option<DateTime<Utc>>,
+}
+
+/// Delivery state of reminders, persisted in the cache across restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReminderState {
+    /// Delivered reminders by reminder id
+    pub delivered: HashMap<String, DeliveredReminder>,
+}
+
+impl ReminderState {
+    /// Delivered reminders that have not been acknowledged, oldest first
+    #[must_use]
+    pub fn active(&self) -> Vec<DeliveredReminder> {
+        let mut active: Vec<_> = self
+            .delivered
+            .values()
+            .filter(|delivered| !delivered.acknowledged)
+            .cloned()
+            .collect();
+        active.sort_by_key(|delivered| delivered.reminder.trigger_at);
+        active
+    }
+
+    /// Reminders to deliver at `now`: newly due ones that haven't been
+    /// delivered yet, plus snoozed ones whose snooze has ended
+    fn pending(&self, due: Vec<Reminder>, now: DateTime<Utc>) -> Vec<Reminder> {
+        let snoozed = self
+            .delivered
+            .values()
+            .filter(|delivered| {
+                !delivered.acknowledged && delivered.snoozed_until.is_some_and(|until| until <= now)
+            })
+            .map(|delivered| delivered.reminder.clone());
+
+        due.into_iter()
+            .filter(|reminder| !self.delivered.contains_key(&reminder.id))
+            .chain(snoozed)
+            .collect()
+    }
+
+    /// Record that `reminder` was delivered at `now`
+    ///
+    /// Re-delivering a snoozed reminder ends its snooze.
+    fn record(&mut self, reminder: Reminder, now: DateTime<Utc>) {
+        self.delivered
+            .entry(reminder.id.clone())
+            .and_modify(|delivered| {
+                delivered.fired_at = now;
+                delivered.snoozed_until = None;
+            })
+            .or_insert(DeliveredReminder {
+                reminder,
+                fired_at: now,
+                acknowledged: false,
+                snoozed_until: None,
+            });
+    }
+
+    /// Mark a reminder acknowledged, returning `false` if it is unknown
+    fn acknowledge(&mut self, id: &str) -> bool {
+        self.delivered.get_mut(id).is_some_and(|delivered| {
+            delivered.acknowledged = true;
+            delivered.snoozed_until = None;
+            true
+        })
+    }
+
+    /// Snooze a reminder until `until`, returning `false` if it is unknown
+    fn snooze(&mut self, id: &str, until: DateTime<Utc>) -> bool {
+        self.delivered.get_mut(id).is_some_and(|delivered| {
+            delivered.acknowledged = false;
+            delivered.snoozed_until = Some(until);
+            true
+        })
+    }
+
+    /// Forget reminders whose trigger is long past and that aren't snoozed
+    ///
+    /// Returns `true` if any reminder was removed.
+    fn prune(&mut self, now: DateTime<Utc>) -> bool {
+        let cutoff = now - chrono::Duration::days(RETENTION_DAYS);
+        let before = self.delivered.len();
+        self.delivered.retain(|_, delivered| {
+            delivered.reminder.trigger_at >= cutoff
+                || delivered.snoozed_until.is_some_and(|until| until >= now)
+        });
+        self.delivered.len() != before
+    }
+}
+
+/// Collect the alarms of all occurrences that trigger in `(from, to]`
+///
+/// Relative triggers are resolved per occurrence, so the query range is
+/// widened by the largest alarm offsets found in the data. Absolute
+/// triggers fire once per stored event or todo. Cancelled items and
+/// completed todos don't produce reminders.
+#[must_use]
+pub fn due_reminders(data: &CalendarData, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Reminder> {
+    let in_window = |at: Option<DateTime<Utc>>| at.is_some_and(|at| at > from && at <= to);
+    let mut reminders = Vec::new();
+
+    // How far before and after its anchor any relative alarm fires
+    let offsets = data
+        .events
+        .iter()
+        .flat_map(|event| &event.alarms)
+        .chain(data.todos.iter().flat_map(|todo| &todo.alarms))
+        .filter_map(|alarm| alarm.trigger_offset_seconds);
+    let max_before =
+        chrono::Duration::seconds(offsets.clone().map(|o| -o).max().unwrap_or(0).max(0));
+    let max_after = chrono::Duration::seconds(offsets.max().unwrap_or(0).max(0));
+
+    // A todo's start may be well before its due time, which decides whether
+    // todos_in_range returns it
+    let max_todo_span = data
+        .todos
+        .iter()
+        .filter_map(|todo| Some(todo.due? - todo.start?))
+        .max()
+        .unwrap_or_else(chrono::Duration::zero)
+        .max(chrono::Duration::zero());
+
+    let range_start = (from - max_after).with_timezone(&Local);
+    let range_end = (to + max_before + chrono::Duration::seconds(1)).with_timezone(&Local);
+
+    for event in data.events_in_range(range_start, range_end) {
+        if event.is_cancelled() {
+            continue;
+        }
+        for (index, alarm) in event.alarms.iter().enumerate() {
+            if alarm.trigger_offset_seconds.is_some() && in_window(alarm.trigger_at) {
+                reminders.extend(event_reminder(&event, index, alarm));
+            }
+        }
+    }
+
+    for todo in data.todos_in_range(range_start, range_end + max_todo_span) {
+        if todo.is_completed() || todo.is_cancelled() {
+            continue;
+        }
+        for (index, alarm) in todo.alarms.iter().enumerate() {
+            if alarm.trigger_offset_seconds.is_some() && in_window(alarm.trigger_at) {
+                reminders.extend(todo_reminder(&todo, index, alarm));
+            }
+        }
+    }
+
+    // Absolute triggers are the same for every occurrence, so fire them once
+    for event in data.events.iter().filter(|event| !event.is_cancelled()) {
+        for (index, alarm) in event.alarms.iter().enumerate() {
+            if alarm.trigger_offset_seconds.is_none() && in_window(alarm.trigger_at) {
+                reminders.extend(event_reminder(event, index, alarm));
+            }
+        }
+    }
+
+    for todo in data
+        .todos
+        .iter()
+        .filter(|todo| !todo.is_completed() && !todo.is_cancelled())
+    {
+        for (index, alarm) in todo.alarms.iter().enumerate() {
+            if alarm.trigger_offset_seconds.is_none() && in_window(alarm.trigger_at) {
+                reminders.extend(todo_reminder(todo, index, alarm));
+            }
+        }
+    }
+
+    reminders.sort_by_key(|reminder| reminder.trigger_at);
+    reminders
+}
+
+/// Build the identifier of one alarm of one occurrence
+fn reminder_id(instance_id: &str, index: usize, trigger_at: DateTime<Utc>) -> String {
+    format!(
+        "{instance_id}:alarm{index}:{}",
+        trigger_at.format("%Y%m%dT%H%M%SZ")
+    )
+}
+
+fn event_reminder(event: &CalendarEvent, index: usize, alarm: &Alarm) -> Option<Reminder> {
+    let trigger_at = alarm.trigger_at?;
+
+    Some(Reminder {
+        id: reminder_id(&event.instance_id, index, trigger_at),
+        uid: event.uid.clone(),
+        instance_id: event.instance_id.clone(),
+        item_type: "event".to_string(),
+        summary: event.summary.clone(),
+        calendar_name: event.calendar_name.clone(),
+        action: alarm.action.clone(),
+        description: alarm.description.clone(),
+        trigger_at,
+        start: Some(event.start),
+        end: Some(event.end),
+        due: None,
+    })
+}
+
+fn todo_reminder(todo: &Todo, index: usize, alarm: &Alarm) -> Option<Reminder> {
+    let trigger_at = alarm.trigger_at?;
+    let instance_id = CalendarEvent::make_instance_id(&todo.uid, todo.recurrence_id);
+
+    Some(Reminder {
+        id: reminder_id(&instance_id, index, trigger_at),
+        uid: todo.uid.clone(),
+        instance_id,
+        item_type: "todo".to_string(),
+        summary: todo.summary.clone(),
+        calendar_name: todo.calendar_name.clone(),
+        action: alarm.action.clone(),
+        description: alarm.description.clone(),
+        trigger_at,
+        start: todo.start,
+        end: None,
+        due: todo.due,
+    })
+}
+
+/// Watches upcoming alarms and delivers them through the configured sinks
+pub struct ReminderScheduler {
+    data: Arc<RwLock<CalendarData>>,
+    cache: CacheManager,
+    sinks: Vec<ReminderSink>,
+    http: Option<HyperClient>,
+    state: RwLock<ReminderState>,
+    sink_timeout: Duration,
+}
+
+impl ReminderScheduler {
+    /// Create a scheduler, restoring delivery state from the cache
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client for webhook sinks cannot be built.
+    pub fn new(
+        data: Arc<RwLock<CalendarData>>,
+        cache: CacheManager,
+        sinks: Vec<ReminderSink>,
+    ) -> Result<Self> {
+        let state = match cache.load_reminders() {
+            Ok(Some(state)) => state,
+            Ok(None) => ReminderState::default(),
+            Err(e) => {
+                warn!("Failed to load reminder state, starting fresh: {}", e);
+                ReminderState::default()
+            }
+        };
+
+        let http = if sinks
+            .iter()
+            .any(|sink| matches!(sink, ReminderSink::Webhook(_)))
+        {
+            Some(build_hyper_client()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            data,
+            cache,
+            sinks,
+            http,
+            state: RwLock::new(state),
+            sink_timeout: SINK_TIMEOUT,
+        })
+    }
+
+    /// Give up on a sink that takes longer than `timeout` to accept a reminder
+    #[must_use]
+    pub const fn with_sink_timeout(mut self, timeout: Duration) -> Self {
+        self.sink_timeout = timeout;
+        self
+    }
+
+    /// Start checking for due alarms every `interval_seconds`
+    pub async fn start(self: Arc<Self>, interval_seconds: u64) {
+        let mut ticker = interval(Duration::from_secs(interval_seconds));
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = self.check(Utc::now()).await {
+                error!("Reminder check failed: {}", e);
+            }
+        }
+    }
+
+    /// Deliver every alarm due at `now` that hasn't been delivered yet
+    ///
+    /// Alarms missed by up to an hour (e.g. while the service was down) are
+    /// still delivered, and snoozed reminders are delivered again once their
+    /// snooze ends. Returns the number of reminders delivered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delivery state cannot be saved.
+    pub async fn check(&self, now: DateTime<Utc>) -> Result<usize> {
+        let from = now - chrono::Duration::minutes(MAX_LATENESS_MINUTES);
+        let due = {
+            let data = self.data.read().await;
+            due_reminders(&data, from, now)
+        };
+
+        let pending = self.state.read().await.pending(due, now);
+
+        let mut delivered_count = 0;
+        for reminder in pending {
+            if self.deliver(&reminder).await {
+                delivered_count += 1;
+                self.state.write().await.record(reminder, now);
+            }
+        }
+
+        let pruned = self.state.write().await.prune(now);
+
+        if delivered_count > 0 || pruned {
+            self.save().await?;
+        }
+
+        Ok(delivered_count)
+    }
+
+    /// Mark a delivered reminder as acknowledged
+    ///
+    /// Returns `false` if no reminder with this id has been delivered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delivery state cannot be saved.
+    pub async fn acknowledge(&self, id: &str) -> Result<bool> {
+        let found = self.state.write().await.acknowledge(id);
+        if found {
+            self.save().await?;
+        }
+        Ok(found)
+    }
+
+    /// Deliver a reminder again at `until`
+    ///
+    /// Returns `false` if no reminder with this id has been delivered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delivery state cannot be saved.
+    pub async fn snooze(&self, id: &str, until: DateTime<Utc>) -> Result<bool> {
+        let found = self.state.write().await.snooze(id, until);
+        if found {
+            self.save().await?;
+        }
+        Ok(found)
+    }
+
+    /// Delivered reminders that have not been acknowledged
+    pub async fn active(&self) -> Vec<DeliveredReminder> {
+        self.state.read().await.active()
+    }
+
+    /// Persist the delivery state to the cache
+    async fn save(&self) -> Result<()> {
+        let state = self.state.read().await.clone();
+        self.cache.save_reminders(&state)
+    }
+
+    /// Send a reminder to every sink
+    ///
+    /// Returns `true` if at least one sink accepted it (or none are configured).
+    /// A sink that doesn't finish within the sink timeout counts as failed,
+    /// so one hanging sink can't hold up later reminders.
+    async fn deliver(&self, reminder: &Reminder) -> bool {
+        info!(
+            "Reminder: {} ({} at {})",
+            reminder.summary, reminder.action, reminder.trigger_at
+        );
+
+        if self.sinks.is_empty() {
+            return true;
+        }
+
+        let mut any_delivered = false;
+        for sink in &self.sinks {
+            match tokio::time::timeout(self.sink_timeout, self.deliver_to(sink, reminder)).await {
+                Ok(Ok(())) => any_delivered = true,
+                Ok(Err(e)) => error!(
+                    "Failed to deliver reminder {} to {:?}: {}",
+                    reminder.id, sink, e
+                ),
+                Err(_) => error!(
+                    "Failed to deliver reminder {} to {:?}: timed out after {:?}",
+                    reminder.id, sink, self.sink_timeout
+                ),
+            }
+        }
+
+        any_delivered
+    }
+
+    async fn deliver_to(&self, sink: &ReminderSink, reminder: &Reminder) -> Result<()> {
+        let payload = serde_json::to_vec(reminder).context("Failed to serialize reminder")?;
+
+        match sink {
+            ReminderSink::Webhook(url) => {
+                let client = self
+                    .http
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("HTTP client not initialized"))?;
+                let request = hyper::Request::post(url.as_str())
+                    .header("content-type", "application/json")
+                    .body(Full::new(Bytes::from(payload)))
+                    .context("Failed to build webhook request")?;
+
+                let response = client
+                    .request(request)
+                    .await
+                    .context("Webhook request failed")?;
+                if !response.status().is_success() {
+                    anyhow::bail!("Webhook returned {}", response.status());
+                }
+            }
+            ReminderSink::Command(command) => {
+                // Killed when the delivery times out
+                let mut child = shell_command(command)
+                    .stdin(Stdio::piped())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .context("Failed to start reminder command")?;
+
+                let written = match child.stdin.take() {
+                    Some(mut stdin) => stdin.write_all(&payload).await,
+                    None => Ok(()),
+                };
+
+                let status = child.wait().await?;
+                if !status.success() {
+                    anyhow::bail!("Reminder command exited with {status}");
+                }
+
+                // A command may succeed without reading the reminder
+                match written {
+                    Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e.into()),
+                    _ => {}
+                }
+            }
+            ReminderSink::Pipe(path) => write_to_pipe(path, &payload).await?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Build a command that runs `command` through the platform shell
+fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = tokio::process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = tokio::process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    shell.arg(command);
+    shell
+}
+
+/// Write one JSON line to a named pipe
+///
+/// Fails instead of blocking when nothing is reading from the pipe.
+#[cfg(unix)]
+async fn write_to_pipe(path: &PathBuf, payload: &[u8]) -> Result<()> {
+    let mut sender = tokio::net::unix::pipe::OpenOptions::new()
+        .open_sender(path)
+        .with_context(|| format!("Failed to open pipe {}", path.display()))?;
+
+    let mut line = payload.to_vec();
+    line.push(b'\n');
+    sender.write_all(&line).await?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn write_to_pipe(path: &PathBuf, _payload: &[u8]) -> Result<()> {
+    anyhow::bail!(
+        "Named pipe sinks are only supported on Unix ({})",
+        path.display()
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::models::{EventStatus, TodoStatus};
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn alarm(offset_minutes: i64) -> Alarm {
+        Alarm {
+            action: "DISPLAY".to_string(),
+            description: Some("Heads up".to_string()),
+            trigger_offset_seconds: Some(offset_minutes * 60),
+            trigger_related: Some("START".to_string()),
+            trigger_at: None,
+        }
+    }
+
+    fn event_with_alarms(
+        uid: &str,
+        start: DateTime<Utc>,
+        rrule: Option<&str>,
+        alarms: Vec<Alarm>,
+    ) -> CalendarEvent {
+        let mut event = CalendarEvent {
+            uid: uid.to_string(),
+            summary: uid.to_string(),
+            description: None,
+            location: None,
+            start,
+            end: start + chrono::Duration::hours(1),
+            calendar_name: "Test".to_string(),
+            calendar_url: "/test".to_string(),
+            calendar_color: None,
+            all_day: false,
+            start_date: None,
+            end_date: None,
+            tzid: None,
+            floating: false,
+            vtimezone: None,
+            rrule: rrule.map(String::from),
+            exdates: Vec::new(),
+            exdate_dates: Vec::new(),
+            rdates: Vec::new(),
+            status: None,
+            etag: None,
+            recurrence_id: None,
+            instance_id: uid.to_string(),
+            series_uid: rrule.map(|_| uid.to_string()),
+            alarms,
+            organizer: None,
+            attendees: Vec::new(),
+            own_partstat: None,
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            transparency: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            geo: None,
+            priority: None,
+            conferences: Vec::new(),
+            href: None,
+        };
+        event.resolve_alarms();
+        event
+    }
+
+    fn test_scheduler(data: CalendarData, dir: &TempDir) -> ReminderScheduler {
+        let cache = CacheManager::new_with_path(dir.path().to_path_buf()).unwrap();
+        ReminderScheduler::new(Arc::new(RwLock::new(data)), cache, Vec::new()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_reminder_sinks() {
+        assert_eq!(
+            "webhook:https://example.com/hook"
+                .parse::<ReminderSink>()
+                .unwrap(),
+            ReminderSink::Webhook("https://example.com/hook".to_string())
+        );
+        assert_eq!(
+            "command:notify-send reminder"
+                .parse::<ReminderSink>()
+                .unwrap(),
+            ReminderSink::Command("notify-send reminder".to_string())
+        );
+        assert_eq!(
+            "pipe:/run/fred-cal.fifo".parse::<ReminderSink>().unwrap(),
+            ReminderSink::Pipe(PathBuf::from("/run/fred-cal.fifo"))
+        );
+
+        assert!("webhook:ftp://example.com".parse::<ReminderSink>().is_err());
+        assert!("email:me@example.com".parse::<ReminderSink>().is_err());
+        assert!("command:".parse::<ReminderSink>().is_err());
+        assert!("no-kind".parse::<ReminderSink>().is_err());
+    }
+
+    #[test]
+    fn test_due_reminders_resolves_each_occurrence() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        data.events.push(event_with_alarms(
+            "standup",
+            start,
+            Some("FREQ=DAILY"),
+            vec![alarm(-15)],
+        ));
+
+        // The alarm of the third occurrence fires at 08:45 on March 4th
+        let at = Utc.with_ymd_and_hms(2026, 3, 4, 8, 45, 0).unwrap();
+        let reminders = due_reminders(&data, at - chrono::Duration::minutes(1), at);
+
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].trigger_at, at);
+        assert_eq!(
+            reminders[0].start,
+            Some(Utc.with_ymd_and_hms(2026, 3, 4, 9, 0, 0).unwrap())
+        );
+        assert_eq!(
+            reminders[0].id,
+            "standup:20260304T090000Z:alarm0:20260304T084500Z"
+        );
+
+        // Nothing fires between occurrences
+        let later = at + chrono::Duration::hours(2);
+        assert!(due_reminders(&data, at, later).is_empty());
+    }
+
+    #[test]
+    fn test_due_reminders_long_offset_and_absolute_trigger() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let absolute = Alarm {
+            trigger_offset_seconds: None,
+            trigger_related: None,
+            trigger_at: Some(Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()),
+            ..alarm(0)
+        };
+        let mut data = CalendarData::new();
+        data.events.push(event_with_alarms(
+            "trip",
+            start,
+            None,
+            vec![alarm(-2 * 24 * 60), absolute],
+        ));
+
+        // Two days before the start
+        let two_days_before = start - chrono::Duration::days(2);
+        let reminders = due_reminders(
+            &data,
+            two_days_before - chrono::Duration::minutes(1),
+            two_days_before,
+        );
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].trigger_at, two_days_before);
+
+        // The absolute trigger fires at its own time
+        let noon = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let reminders = due_reminders(&data, noon - chrono::Duration::minutes(1), noon);
+        assert_eq!(reminders.len(), 1);
+        assert!(reminders[0].id.ends_with(":alarm1:20260301T120000Z"));
+    }
+
+    #[test]
+    fn test_due_reminders_skips_cancelled_and_completed() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        let mut cancelled = event_with_alarms("cancelled", start, None, vec![alarm(-10)]);
+        cancelled.status = Some(EventStatus::Cancelled);
+        data.events.push(cancelled);
+
+        let mut todo = Todo {
+            uid: "todo".to_string(),
+            summary: "File taxes".to_string(),
+            description: None,
+            due: Some(start),
+            start: None,
+            completed: None,
+            priority: None,
+            percent_complete: None,
+            status: TodoStatus::NeedsAction,
+            calendar_name: "Tasks".to_string(),
+            calendar_url: "/tasks".to_string(),
+            etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: vec![alarm(-10)],
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: None,
+            child_uids: Vec::new(),
+            href: None,
+        };
+        todo.resolve_alarms();
+        data.todos.push(todo.clone());
+
+        let at = start - chrono::Duration::minutes(10);
+        let reminders = due_reminders(&data, at - chrono::Duration::minutes(1), at);
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].item_type, "todo");
+        assert_eq!(reminders[0].due, Some(start));
+
+        todo.status = TodoStatus::Completed;
+        data.todos = vec![todo];
+        assert!(due_reminders(&data, at - chrono::Duration::minutes(1), at).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_does_not_refire_after_restart() {
+        let dir = TempDir::new().unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        data.events
+            .push(event_with_alarms("meeting", start, None, vec![alarm(-15)]));
+
+        let now = start - chrono::Duration::minutes(14);
+        let scheduler = test_scheduler(data.clone(), &dir);
+        assert_eq!(scheduler.check(now).await.unwrap(), 1);
+        assert_eq!(scheduler.check(now).await.unwrap(), 0);
+        assert_eq!(scheduler.active().await.len(), 1);
+
+        // A new scheduler restores the delivered state from the cache
+        let restarted = test_scheduler(data, &dir);
+        assert_eq!(restarted.check(now).await.unwrap(), 0);
+        assert_eq!(restarted.active().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_snooze_and_acknowledge() {
+        let dir = TempDir::new().unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        data.events
+            .push(event_with_alarms("meeting", start, None, vec![alarm(-15)]));
+
+        let now = start - chrono::Duration::minutes(15);
+        let scheduler = test_scheduler(data, &dir);
+        assert_eq!(scheduler.check(now).await.unwrap(), 1);
+        let id = scheduler.active().await[0].reminder.id.clone();
+
+        // Snoozed reminders are delivered again once the snooze ends
+        let until = now + chrono::Duration::minutes(5);
+        assert!(scheduler.snooze(&id, until).await.unwrap());
+        assert_eq!(
+            scheduler
+                .check(until - chrono::Duration::minutes(1))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(scheduler.check(until).await.unwrap(), 1);
+        assert_eq!(scheduler.check(until).await.unwrap(), 0);
+
+        // Acknowledged reminders are no longer active
+        assert!(scheduler.acknowledge(&id).await.unwrap());
+        assert!(scheduler.active().await.is_empty());
+        assert!(!scheduler.acknowledge("unknown").await.unwrap());
+        assert!(!scheduler.snooze("unknown", until).await.unwrap());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_sink_receives_json() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("reminder.json");
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        data.events
+            .push(event_with_alarms("meeting", start, None, vec![alarm(-15)]));
+
+        let cache = CacheManager::new_with_path(dir.path().to_path_buf()).unwrap();
+        let sink = ReminderSink::Command(format!("cat > '{}'", output.display()));
+        let scheduler =
+            ReminderScheduler::new(Arc::new(RwLock::new(data)), cache, vec![sink]).unwrap();
+
+        assert_eq!(
+            scheduler
+                .check(start - chrono::Duration::minutes(15))
+                .await
+                .unwrap(),
+            1
+        );
+
+        let written: Reminder =
+            serde_json::from_str(&std::fs::read_to_string(output).unwrap()).unwrap();
+        assert_eq!(written.uid, "meeting");
+        assert_eq!(written.action, "DISPLAY");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hanging_sink_times_out() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("reminder.json");
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        data.events
+            .push(event_with_alarms("meeting", start, None, vec![alarm(-15)]));
+
+        let cache = CacheManager::new_with_path(dir.path().to_path_buf()).unwrap();
+        let sinks = vec![
+            ReminderSink::Command("sleep 30".to_string()),
+            ReminderSink::Command(format!("cat > '{}'", output.display())),
+        ];
+        let scheduler = ReminderScheduler::new(Arc::new(RwLock::new(data)), cache, sinks)
+            .unwrap()
+            .with_sink_timeout(Duration::from_millis(200));
+
+        let started = std::time::Instant::now();
+        let now = start - chrono::Duration::minutes(15);
+        assert_eq!(scheduler.check(now).await.unwrap(), 1);
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(output.exists());
+    }
+
+    #[tokio::test]
+    async fn test_failed_sink_is_retried() {
+        let dir = TempDir::new().unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        data.events
+            .push(event_with_alarms("meeting", start, None, vec![alarm(-15)]));
+
+        let cache = CacheManager::new_with_path(dir.path().to_path_buf()).unwrap();
+        let sink = ReminderSink::Pipe(dir.path().join("missing.fifo"));
+        let scheduler =
+            ReminderScheduler::new(Arc::new(RwLock::new(data)), cache, vec![sink]).unwrap();
+
+        // Nothing accepted the reminder, so it stays pending
+        let now = start - chrono::Duration::minutes(15);
+        assert_eq!(scheduler.check(now).await.unwrap(), 0);
+        assert!(scheduler.active().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_sink_posts_json() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header("content-type", "application/json"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = TempDir::new().unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let mut data = CalendarData::new();
+        data.events
+            .push(event_with_alarms("meeting", start, None, vec![alarm(-15)]));
+
+        let cache = CacheManager::new_with_path(dir.path().to_path_buf()).unwrap();
+        let sink = ReminderSink::Webhook(format!("{}/hook", server.uri()));
+        let scheduler =
+            ReminderScheduler::new(Arc::new(RwLock::new(data)), cache, vec![sink]).unwrap();
+
+        let now = start - chrono::Duration::minutes(15);
+        assert_eq!(scheduler.check(now).await.unwrap(), 1);
+
+        let requests = server.received_requests().await.unwrap();
+        let body: Reminder = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body.summary, "meeting");
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (C) 2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+use crate::models::Todo;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// A todo together with its subtasks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TodoNode {
+    /// The todo itself
+    #[serde(flatten)]
+    pub todo: Todo,
+
+    /// Subtasks, in the order the todos were given
+    pub children: Vec<Self>,
+}
+
+/// Arrange todos into trees using their RELATED-TO links
+///
+/// A todo is placed under its `parent_uid`, or under a todo listing it in
+/// `child_uids`. Todos whose parent isn't among `todos` become roots, and
+/// links that would form a cycle are ignored.
+///
+/// With `derive_progress`, the `percent_complete` of every unfinished todo
+/// with subtasks is replaced by the average progress of its subtasks:
+/// completed subtasks count as 100%, cancelled ones are left out.
+#[must_use]
+pub fn build_todo_tree(todos: Vec<Todo>, derive_progress: bool) -> Vec<TodoNode> {
+    let parents = parent_links(&todos);
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); todos.len()];
+    let mut roots = Vec::new();
+    for (index, parent) in parents.into_iter().enumerate() {
+        match parent {
+            Some(parent) => children[parent].push(index),
+            None => roots.push(index),
+        }
+    }
+
+    let mut slots: Vec<Option<Todo>> = todos.into_iter().map(Some).collect();
+    roots
+        .into_iter()
+        .filter_map(|root| build_node(root, &mut slots, &children, derive_progress))
+        .collect()
+}
+
+/// Resolve the parent of every todo to its index, dropping cyclic links
+fn parent_links(todos: &[Todo]) -> Vec<Option<usize>> {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, todo) in todos.iter().enumerate() {
+        index.entry(todo.uid.as_str()).or_insert(i);
+    }
+
+    let mut parents: Vec<Option<usize>> = todos
+        .iter()
+        .map(|todo| {
+            todo.parent_uid
+                .as_deref()
+                .and_then(|uid| index.get(uid).copied())
+        })
+        .collect();
+
+    // A parent may list its children instead
+    for (i, todo) in todos.iter().enumerate() {
+        for child in todo
+            .child_uids
+            .iter()
+            .filter_map(|uid| index.get(uid.as_str()))
+        {
+            if parents[*child].is_none() {
+                parents[*child] = Some(i);
+            }
+        }
+    }
+
+    // Walk up from every todo and cut the link that closes a cycle
+    for start in 0..todos.len() {
+        let mut seen = HashSet::from([start]);
+        let mut current = start;
+        while let Some(parent) = parents[current] {
+            if !seen.insert(parent) {
+                parents[current] = None;
+                break;
+            }
+            current = parent;
+        }
+    }
+
+    parents
+}
+
+/// Build the node for `index` and, recursively, its subtasks
+fn build_node(
+    index: usize,
+    slots: &mut [Option<Todo>],
+    children: &[Vec<usize>],
+    derive_progress: bool,
+) -> Option<TodoNode> {
+    let mut todo = slots[index].take()?;
+    let subtasks: Vec<TodoNode> = children[index]
+        .iter()
+        .filter_map(|&child| build_node(child, slots, children, derive_progress))
+        .collect();
+
+    if derive_progress
+        && !todo.is_completed()
+        && let Some(progress) = subtask_progress(&subtasks)
+    {
+        todo.percent_complete = Some(progress);
+    }
+
+    Some(TodoNode {
+        todo,
+        children: subtasks,
+    })
+}
+
+/// Average progress of subtasks, or `None` if none of them count
+fn subtask_progress(subtasks: &[TodoNode]) -> Option<u8> {
+    let progress: Vec<u32> = subtasks
+        .iter()
+        .filter(|node| !node.todo.is_cancelled())
+        .map(|node| {
+            if node.todo.is_completed() {
+                100
+            } else {
+                u32::from(node.todo.percent_complete.unwrap_or(0))
+            }
+        })
+        .collect();
+
+    let count = u32::try_from(progress.len()).ok().filter(|&n| n > 0)?;
+    let total: u32 = progress.iter().sum();
+    u8::try_from((total + count / 2) / count).ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::models::TodoStatus;
+
+    fn test_todo(uid: &str, parent_uid: Option<&str>) -> Todo {
+        Todo {
+            uid: uid.to_string(),
+            summary: uid.to_string(),
+            description: None,
+            due: None,
+            start: None,
+            completed: None,
+            priority: None,
+            percent_complete: None,
+            status: TodoStatus::NeedsAction,
+            calendar_name: "Tasks".to_string(),
+            calendar_url: "/tasks".to_string(),
+            etag: None,
+            rrule: None,
+            recurrence_id: None,
+            tzid: None,
+            floating: false,
+            due_date: None,
+            start_date: None,
+            alarms: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            class: None,
+            sequence: None,
+            created: None,
+            last_modified: None,
+            parent_uid: parent_uid.map(String::from),
+            child_uids: Vec::new(),
+            href: None,
+        }
+    }
+
+    fn uids(nodes: &[TodoNode]) -> Vec<&str> {
+        nodes.iter().map(|node| node.todo.uid.as_str()).collect()
+    }
+
+    #[test]
+    fn test_build_todo_tree_nests_subtasks() {
+        let mut listed_child = test_todo("listed", None);
+        listed_child.summary = "Listed by its parent".to_string();
+        let mut project = test_todo("project", None);
+        project.child_uids = vec!["listed".to_string()];
+
+        let tree = build_todo_tree(
+            vec![
+                test_todo("step-1", Some("project")),
+                project,
+                test_todo("step-1a", Some("step-1")),
+                listed_child,
+                test_todo("orphan", Some("missing")),
+            ],
+            false,
+        );
+
+        assert_eq!(uids(&tree), vec!["project", "orphan"]);
+        assert_eq!(uids(&tree[0].children), vec!["step-1", "listed"]);
+        assert_eq!(uids(&tree[0].children[0].children), vec!["step-1a"]);
+        assert_eq!(tree[0].todo.percent_complete, None);
+    }
+
+    #[test]
+    fn test_build_todo_tree_breaks_cycles() {
+        let tree = build_todo_tree(
+            vec![
+                test_todo("a", Some("b")),
+                test_todo("b", Some("a")),
+                test_todo("self", Some("self")),
+            ],
+            false,
+        );
+
+        // Every todo is still returned exactly once
+        assert_eq!(uids(&tree), vec!["b", "self"]);
+        assert_eq!(uids(&tree[0].children), vec!["a"]);
+        assert!(tree[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_build_todo_tree_derives_progress() {
+        let mut done = test_todo("done", Some("project"));
+        done.status = TodoStatus::Completed;
+        let mut cancelled = test_todo("cancelled", Some("project"));
+        cancelled.status = TodoStatus::Cancelled;
+        let mut half = test_todo("half", Some("project"));
+        half.percent_complete = Some(80);
+        let mut nested = test_todo("nested", Some("half"));
+        nested.percent_complete = Some(50);
+
+        let tree = build_todo_tree(
+            vec![
+                test_todo("project", None),
+                done,
+                cancelled,
+                half,
+                nested,
+                test_todo("leaf", None),
+            ],
+            true,
+        );
+
+        // "half" takes its only subtask's progress, then the project
+        // averages 100% and 50%, ignoring the cancelled subtask
+        assert_eq!(tree[0].children[2].todo.percent_complete, Some(50));
+        assert_eq!(tree[0].todo.percent_complete, Some(75));
+
+        // Todos without subtasks keep their own progress
+        assert_eq!(tree[1].todo.percent_complete, None);
+    }
+}
@@ -4,29 +4,133 @@
 // https://opensource.org/licenses/MIT.
 
 use crate::cache::CacheManager;
-use crate::models::{CalendarData, CalendarEvent, Todo};
-use crate::recurrence::{RecurrenceConfig, expand_recurring_event};
+use crate::models::{
+    Alarm, Attendee, CalendarData, CalendarEvent, CalendarObject, Conference, Geo, Journal,
+    Organizer, Todo,
+};
+use crate::timezone::TimezoneRegistry;
 use anyhow::Result;
-use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use chrono_tz::Tz;
-use fast_dav_rs::CalDavClient;
+use fast_dav_rs::{
+    CalDavClient, Depth, SyncItem, SyncResponse, build_sync_collection_body, map_sync_response,
+    parse_multistatus_bytes,
+};
 use futures::future::join_all;
 use icalendar::{
-    Calendar, CalendarDateTime, Component, DatePerhapsTime, Event, EventLike, Todo as IcalTodo,
+    Calendar, CalendarComponent, CalendarDateTime, Component, DatePerhapsTime, Event, EventLike,
+    Property, Todo as IcalTodo, ValueType,
 };
+use quick_xml::events::Event as XmlEvent;
+use std::collections::HashSet;
+use std::fmt;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tokio::sync::RwLock;
 use tokio::time::{Duration, interval};
 
 /// Batch size for calendar-multiget requests
 const BATCH_SIZE: usize = 500;
 
+/// Number of changes requested per sync-collection page
+const SYNC_PAGE_LIMIT: u32 = 1000;
+
+/// Maximum number of sync-collection pages fetched in one sync
+const MAX_SYNC_PAGES: usize = 100;
+
+/// The server rejected a sync token (`DAV:valid-sync-token` precondition)
+#[derive(Debug)]
+struct InvalidSyncToken;
+
+impl fmt::Display for InvalidSyncToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sync token rejected by the server (DAV:valid-sync-token)")
+    }
+}
+
+impl std::error::Error for InvalidSyncToken {}
+
+/// Time range of events and todos kept in sync with the server
+///
+/// The bounds are whole days around the current UTC date, so the window
+/// moves once a day. A recurring series is fetched as a whole when any of
+/// its occurrences overlaps the window (RFC 4791, section 9.9), and todos
+/// without dates always match. Journals are not limited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncWindow {
+    /// How many days before today to sync (`None` = unbounded)
+    pub past_days: Option<u32>,
+    /// How many days after today to sync (`None` = unbounded)
+    pub future_days: Option<u32>,
+}
+
+impl SyncWindow {
+    /// Whether either end of the window is limited
+    #[must_use]
+    pub const fn is_bounded(&self) -> bool {
+        self.past_days.is_some() || self.future_days.is_some()
+    }
+
+    /// Start and end of the window on the day of `now` (`None` = unbounded)
+    ///
+    /// The end is exclusive and falls after the last day in the window.
+    #[must_use]
+    pub fn bounds(&self, now: DateTime<Utc>) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let today = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+        let start = self
+            .past_days
+            .and_then(|days| today.checked_sub_signed(chrono::Duration::days(i64::from(days))));
+        let end = self
+            .future_days
+            .and_then(|days| today.checked_add_signed(chrono::Duration::days(i64::from(days) + 1)));
+        (start, end)
+    }
+
+    /// Whether the range `start..end` lies entirely inside the window
+    #[must_use]
+    pub fn covers(&self, start: DateTime<Utc>, end: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        let (window_start, window_end) = self.bounds(now);
+        window_start.is_none_or(|window_start| start >= window_start)
+            && window_end.is_none_or(|window_end| end <= window_end)
+    }
+
+    /// The window as calendar-query `time-range` values for a component
+    fn time_range(&self, component: &str, now: DateTime<Utc>) -> (Option<String>, Option<String>) {
+        // Journals without a DTSTART never match a time-range
+        if component == "VJOURNAL" {
+            return (None, None);
+        }
+        let (start, end) = self.bounds(now);
+        let format = |bound: DateTime<Utc>| bound.format("%Y%m%dT%H%M%SZ").to_string();
+        (start.map(format), end.map(format))
+    }
+
+    /// Combine a calendar's `CTag` with the window it was synced for
+    ///
+    /// Once the window moves, resources may have entered or left it even
+    /// though the calendar itself is unchanged.
+    fn change_tag(&self, ctag: &str, now: DateTime<Utc>) -> String {
+        if !self.is_bounded() {
+            return ctag.to_string();
+        }
+        let (start, end) = self.time_range("VEVENT", now);
+        format!(
+            "{ctag} {}/{}",
+            start.unwrap_or_default(),
+            end.unwrap_or_default()
+        )
+    }
+}
+
 /// Manages synchronization with `CalDAV` server
 pub struct SyncManager {
     client: Arc<CalDavClient>,
     cache: Arc<CacheManager>,
     data: Arc<RwLock<CalendarData>>,
     calendar_colors: Arc<RwLock<std::collections::HashMap<String, String>>>,
+    calendar_components: Arc<RwLock<std::collections::HashMap<String, Vec<&'static str>>>>,
+    sync_token_resets: Arc<AtomicUsize>,
+    window: SyncWindow,
 }
 
 impl SyncManager {
@@ -60,15 +164,39 @@ impl SyncManager {
             cache: Arc::new(cache),
             data,
             calendar_colors: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            calendar_components: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            sync_token_resets: Arc::new(AtomicUsize::new(0)),
+            window: SyncWindow::default(),
         })
     }
 
+    /// Only sync events and todos inside `window`
+    #[must_use]
+    pub const fn with_sync_window(mut self, window: SyncWindow) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// The time range kept in sync with the server
+    #[must_use]
+    pub const fn sync_window(&self) -> SyncWindow {
+        self.window
+    }
+
     /// Get a read-only reference to the calendar data
     #[must_use]
     pub fn data(&self) -> Arc<RwLock<CalendarData>> {
         Arc::clone(&self.data)
     }
 
+    /// Number of sync tokens the server rejected since startup
+    ///
+    /// Each rejection clears the token and resyncs the calendar from scratch.
+    #[must_use]
+    pub fn sync_token_resets(&self) -> usize {
+        self.sync_token_resets.load(Ordering::Relaxed)
+    }
+
     /// Perform a full sync with the `CalDAV` server
     ///
     /// # Errors
@@ -87,6 +215,8 @@ impl SyncManager {
             .await?
             .ok_or_else(|| anyhow::anyhow!("No principal returned"))?;
 
+        let user_addresses = self.discover_user_addresses(&principal).await;
+
         let homes = self.client.discover_calendar_home_set(&principal).await?;
         let home = homes
             .first()
@@ -95,6 +225,8 @@ impl SyncManager {
         let calendars = self.client.list_calendars(home).await?;
         debug!("Found {} calendars", calendars.len());
 
+        let ctags = self.discover_ctags(home).await;
+
         // Log sync tokens from calendar list (if provided by server)
         for calendar in &calendars {
             let name = calendar
@@ -123,7 +255,7 @@ impl SyncManager {
         // Track calendar URLs we see during this sync
         let mut active_calendar_urls = std::collections::HashSet::new();
 
-        // Store calendar colors first (quick, can be done sequentially)
+        // Store calendar colors and components first (quick, can be done sequentially)
         for calendar in &calendars {
             let calendar_url = &calendar.href;
             active_calendar_urls.insert(calendar_url.clone());
@@ -134,58 +266,61 @@ impl SyncManager {
                     .await
                     .insert(calendar_url.clone(), color.clone());
             }
+
+            self.calendar_components.write().await.insert(
+                calendar_url.clone(),
+                queried_components(&calendar.supported_components),
+            );
         }
 
         // Process all calendars concurrently
+        let now = Utc::now();
         let sync_tasks: Vec<_> = calendars
             .iter()
-            .map(|calendar| self.sync_single_calendar(calendar, supports_sync))
+            .map(|calendar| {
+                // Prefer the CTag, the collection's sync-token changes the same way
+                let ctag = ctags
+                    .get(&calendar.href)
+                    .or(calendar.sync_token.as_ref())
+                    .map(|ctag| self.window.change_tag(ctag, now));
+                async move {
+                    self.sync_single_calendar(calendar, supports_sync, ctag.as_deref())
+                        .await;
+                }
+            })
             .collect();
 
         // Execute all calendar syncs concurrently
         join_all(sync_tasks).await;
 
-        // Clean up events/todos from calendars that no longer exist
-        let (removed_events, removed_todos) = {
+        // Work out the user's own participation status on every event
+        if !user_addresses.is_empty() {
             let mut data = self.data.write().await;
+            for event in &mut data.events {
+                event.own_partstat = event.partstat_for(&user_addresses);
+            }
+        }
 
-            let initial_events = data.events.len();
-            let initial_todos = data.todos.len();
-
-            // Remove events from calendars not in the active list
-            data.events
-                .retain(|e| active_calendar_urls.contains(&e.calendar_url));
-
-            // Remove todos from calendars not in the active list
-            data.todos
-                .retain(|t| active_calendar_urls.contains(&t.calendar_url));
-
-            // Remove sync tokens from calendars not in the active list
-            data.sync_tokens
-                .retain(|url, _| active_calendar_urls.contains(url));
-
-            let removed = (
-                initial_events - data.events.len(),
-                initial_todos - data.todos.len(),
-            );
-
-            drop(data);
-            removed
-        };
+        // Clean up events/todos/journals from calendars that no longer exist
+        let (removed_events, removed_todos, removed_journals) = self
+            .data
+            .write()
+            .await
+            .retain_calendars(&active_calendar_urls);
 
-        if removed_events > 0 || removed_todos > 0 {
+        if removed_events > 0 || removed_todos > 0 || removed_journals > 0 {
             info!(
-                "Cleaned up {} events and {} todos from deleted calendars",
-                removed_events, removed_todos
+                "Cleaned up {} events, {} todos and {} journals from deleted calendars",
+                removed_events, removed_todos, removed_journals
             );
         }
 
         // Update last sync time and save cache
-        let (event_count, todo_count) = {
+        let (event_count, todo_count, journal_count) = {
             let mut data = self.data.write().await;
             data.last_sync = Utc::now();
 
-            let counts = (data.events.len(), data.todos.len());
+            let counts = (data.events.len(), data.todos.len(), data.journals.len());
 
             // Save to cache
             self.cache.save(&data)?;
@@ -195,9 +330,10 @@ impl SyncManager {
         }; // Write lock dropped here
 
         info!(
-            "Sync complete: {} events, {} todos (from {} calendars)",
+            "Sync complete: {} events, {} todos, {} journals (from {} calendars)",
             event_count,
             todo_count,
+            journal_count,
             calendars.len()
         );
 
@@ -205,10 +341,15 @@ impl SyncManager {
     }
 
     /// Sync a single calendar with appropriate strategy
+    ///
+    /// A calendar whose `ctag` matches the one stored after its last
+    /// successful sync hasn't changed and is skipped. The `ctag` is only
+    /// stored once every resource of the calendar was fetched.
     async fn sync_single_calendar(
         &self,
         calendar: &fast_dav_rs::CalendarInfo,
         supports_sync: bool,
+        ctag: Option<&str>,
     ) {
         let calendar_name = calendar
             .displayname
@@ -216,10 +357,31 @@ impl SyncManager {
             .unwrap_or_else(|| "Unnamed".to_string());
         let calendar_url = calendar.href.clone();
 
+        if let Some(ctag) = ctag
+            && self
+                .data
+                .read()
+                .await
+                .ctags
+                .get(&calendar_url)
+                .map(String::as_str)
+                == Some(ctag)
+        {
+            debug!(
+                "Calendar {} unchanged (ctag {}), skipping",
+                calendar_name, ctag
+            );
+            return;
+        }
+
         debug!("Syncing calendar: {}", calendar_name);
 
-        // Choose sync strategy
-        let sync_result = if supports_sync {
+        // Choose sync strategy. sync-collection reports changes regardless
+        // of the window, so a windowed sync compares ETags instead.
+        let sync_result = if supports_sync
+            && !self.window.is_bounded()
+            && !self.incremental_unsupported(&calendar_url).await
+        {
             debug!(
                 "Using sync_collection for {} (subsequent sync)",
                 calendar_name
@@ -228,7 +390,7 @@ impl SyncManager {
                 .sync_calendar_incremental(&calendar_url, &calendar_name)
                 .await
             {
-                Ok(()) => Ok(()),
+                Ok(complete) => Ok(complete),
                 Err(e) => {
                     warn!(
                         "Incremental sync failed for {}, falling back to time-range: {}",
@@ -238,23 +400,74 @@ impl SyncManager {
                 }
             }
         } else {
-            debug!(
-                "Using time-range query for {} (faster than full sync)",
-                calendar_name
-            );
-            self.sync_calendar_full(&calendar_url, &calendar_name).await
+            debug!("Using ETag comparison for {}", calendar_name);
+            match self
+                .sync_calendar_etag_diff(&calendar_url, &calendar_name)
+                .await
+            {
+                Ok(complete) => Ok(complete),
+                Err(e) => {
+                    warn!(
+                        "ETag sync failed for {}, falling back to time-range: {}",
+                        calendar_name, e
+                    );
+                    self.sync_calendar_full(&calendar_url, &calendar_name).await
+                }
+            }
         };
 
-        if let Err(e) = sync_result {
-            error!(
-                "Failed to sync calendar {} at {}: {:?}",
-                calendar_name, calendar_url, e
-            );
+        match sync_result {
+            Ok(true) => {
+                if let Some(ctag) = ctag {
+                    self.data
+                        .write()
+                        .await
+                        .ctags
+                        .insert(calendar_url, ctag.to_string());
+                }
+            }
+            Ok(false) => {
+                warn!(
+                    "Calendar {} was only partly synced, it will be fetched again",
+                    calendar_name
+                );
+            }
+            Err(e) => {
+                error!(
+                    "Failed to sync calendar {} at {}: {:?}",
+                    calendar_name, calendar_url, e
+                );
+            }
         }
     }
 
+    /// Kinds of components to query in a calendar
+    ///
+    /// Querying a kind the calendar doesn't offer fails on some servers.
+    async fn components(&self, calendar_url: &str) -> Vec<&'static str> {
+        self.calendar_components
+            .read()
+            .await
+            .get(calendar_url)
+            .cloned()
+            .unwrap_or_else(|| COMPONENTS.to_vec())
+    }
+
+    /// Whether the server returned no sync token for a calendar before
+    async fn incremental_unsupported(&self, calendar_url: &str) -> bool {
+        self.data
+            .read()
+            .await
+            .sync_tokens
+            .get(calendar_url)
+            .is_some_and(|token| token == "NO_SYNC")
+    }
+
     /// Perform incremental sync using `WebDAV` sync-collection
     ///
+    /// Returns whether every changed resource was fetched. If not, the sync
+    /// token isn't stored, so the next sync reports the changes again.
+    ///
     /// # Errors
     ///
     /// Returns an error if the sync-collection query fails or sync token is invalid.
@@ -262,25 +475,8 @@ impl SyncManager {
         &self,
         calendar_url: &str,
         calendar_name: &str,
-    ) -> Result<()> {
-        // Get sync token from cache
-        let sync_token = {
-            let data = self.data.read().await;
-            data.sync_tokens.get(calendar_url).cloned()
-        };
-
-        // Convert empty string to None (empty string is just a marker that calendar has been synced)
-        // If token is "NO_SYNC", this calendar should never use incremental sync
-        let sync_token = sync_token.and_then(|t| {
-            if t.is_empty() {
-                None
-            } else if t == "NO_SYNC" {
-                // This shouldn't happen - NO_SYNC should prevent us from getting here
-                None
-            } else {
-                Some(t)
-            }
-        });
+    ) -> Result<bool> {
+        let sync_token = self.stored_sync_token(calendar_url).await;
 
         info!(
             "Incremental sync for {} with token: {}",
@@ -290,52 +486,145 @@ impl SyncManager {
                 .map_or("none (first incremental sync)", |t| t)
         );
 
-        // Perform sync-collection query
-        // Pass false for include_data - iCloud doesn't return data in sync-collection
-        // We'll fetch the data separately for changed items
-        // Set a limit - some servers (like iCloud) may require this to return sync tokens
-        let sync_response = self
-            .client
-            .sync_collection(calendar_url, sync_token.as_deref(), Some(1000), false)
-            .await?;
-
-        debug!(
-            "sync_collection returned {} items for {}",
-            sync_response.items.len(),
-            calendar_name
-        );
-        debug!(
-            "sync_collection response: sync_token={:?}, items_count={}",
-            sync_response.sync_token,
-            sync_response.items.len()
-        );
+        let mut token = sync_token;
+        let mut totals = (0, 0, 0, 0);
+        let mut failed = 0;
+        let mut page = 1;
+        let mut reset = false;
+
+        // Without a token the server lists every resource, so anything
+        // else stored for the calendar is gone
+        let mut members = token.is_none().then(HashSet::new);
+
+        // A truncated response only covers part of the changes; its token
+        // continues from there. Pages are applied as they arrive, but the
+        // token is only stored once the last one is in, so a failure part
+        // way resumes from the previous token.
+        let final_token = loop {
+            let sync_response = match self
+                .sync_collection_page(calendar_url, token.as_deref())
+                .await
+            {
+                Ok(response) => response,
+                Err(e) if !reset && token.is_some() && e.is::<InvalidSyncToken>() => {
+                    warn!(
+                        "Sync token for {} was rejected, resyncing from scratch",
+                        calendar_name
+                    );
+                    self.data.write().await.sync_tokens.remove(calendar_url);
+                    self.sync_token_resets.fetch_add(1, Ordering::Relaxed);
+                    reset = true;
+                    token = None;
+                    members = Some(HashSet::new());
+                    page = 1;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
 
-        let mut added_events = 0;
-        let mut added_todos = 0;
-        let mut deleted_count = 0;
+            debug!(
+                "sync_collection page {} returned {} items for {}: sync_token={:?}",
+                page,
+                sync_response.items.len(),
+                calendar_name,
+                sync_response.sync_token
+            );
 
-        // Separate deleted items from changed items
-        let mut hrefs_to_fetch = Vec::new();
+            let truncated = is_truncated(&sync_response);
+            if let Some(members) = &mut members {
+                members.extend(
+                    sync_response
+                        .items
+                        .iter()
+                        .filter(|item| !item.is_deleted && !item.href.ends_with('/'))
+                        .map(|item| item.href.clone()),
+                );
+            }
+            let counts = self
+                .apply_sync_items(calendar_url, calendar_name, &sync_response.items)
+                .await;
+            totals.0 += counts.0;
+            totals.1 += counts.1;
+            totals.2 += counts.2;
+            totals.3 += counts.3;
+            failed += counts.4;
+
+            if !truncated {
+                break sync_response.sync_token;
+            }
 
-        for item in &sync_response.items {
-            if item.is_deleted {
-                deleted_count += self.process_deleted_item(&item.href).await;
-            } else if !item.href.ends_with('/') {
-                // Skip calendar collections, collect .ics files to fetch
-                hrefs_to_fetch.push(item.href.clone());
+            let Some(next_token) = sync_response.sync_token else {
+                anyhow::bail!("Truncated sync-collection response without a sync token");
+            };
+            if token.as_ref() == Some(&next_token) {
+                anyhow::bail!("Truncated sync-collection response made no progress");
+            }
+            if page >= MAX_SYNC_PAGES {
+                anyhow::bail!("sync-collection still truncated after {page} pages");
             }
+
+            debug!(
+                "sync_collection for {} truncated, continuing",
+                calendar_name
+            );
+            token = Some(next_token);
+            page += 1;
+        };
+        if let Some(members) = members {
+            totals.3 += self
+                .data
+                .write()
+                .await
+                .retain_resources(calendar_url, &members);
         }
+        let (added_events, added_todos, added_journals, deleted_count) = totals;
 
-        if !hrefs_to_fetch.is_empty() {
-            let (events, todos) = self
-                .batch_fetch_calendar_items(calendar_url, calendar_name, &hrefs_to_fetch)
+        if failed == 0 {
+            self.store_sync_token(calendar_url, calendar_name, final_token)
                 .await;
-            added_events += events;
-            added_todos += todos;
         }
 
-        // Store new sync token
-        if let Some(new_token) = &sync_response.sync_token {
+        info!(
+            "Incremental sync for {}: +{} events, +{} todos, +{} journals, -{} deleted, {} failed",
+            calendar_name, added_events, added_todos, added_journals, deleted_count, failed
+        );
+
+        Ok(failed == 0)
+    }
+
+    /// Get the sync token stored for a calendar, if it can be used
+    async fn stored_sync_token(&self, calendar_url: &str) -> Option<String> {
+        // Get sync token from cache
+        let sync_token = {
+            let data = self.data.read().await;
+            data.sync_tokens.get(calendar_url).cloned()
+        };
+
+        // Convert empty string to None (empty string is just a marker that calendar has been synced)
+        // If token is "NO_SYNC", this calendar should never use incremental sync
+        sync_token.and_then(|t| {
+            if t.is_empty() {
+                None
+            } else if t == "NO_SYNC" {
+                // This shouldn't happen - NO_SYNC should prevent us from getting here
+                None
+            } else {
+                Some(t)
+            }
+        })
+    }
+
+    /// Store the token of a completed incremental sync
+    ///
+    /// Without a token the server doesn't support sync tokens for the
+    /// calendar, which is marked to never use incremental sync again.
+    async fn store_sync_token(
+        &self,
+        calendar_url: &str,
+        calendar_name: &str,
+        sync_token: Option<String>,
+    ) {
+        if let Some(new_token) = &sync_token {
             // Check if token changed before logging
             let token_changed = {
                 let data = self.data.read().await;
@@ -364,24 +653,87 @@ impl SyncManager {
                 .insert(calendar_url.to_string(), "NO_SYNC".to_string());
             drop(data);
         }
+    }
 
-        info!(
-            "Incremental sync for {}: +{} events, +{} todos, -{} deleted",
-            calendar_name, added_events, added_todos, deleted_count
-        );
+    /// Request one page of changes with sync-collection
+    ///
+    /// Like [`CalDavClient::sync_collection`], but reads the error body so a
+    /// rejected token is reported as [`InvalidSyncToken`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the REPORT fails or its response can't be parsed.
+    async fn sync_collection_page(
+        &self,
+        calendar_url: &str,
+        sync_token: Option<&str>,
+    ) -> Result<SyncResponse> {
+        // Pass false for include_data - iCloud doesn't return data in sync-collection
+        // We'll fetch the data separately for changed items
+        // Set a limit - some servers (like iCloud) may require this to return sync tokens
+        let body = build_sync_collection_body(sync_token, Some(SYNC_PAGE_LIMIT), false);
+        let response = self.client.report(calendar_url, Depth::One, &body).await?;
 
-        Ok(())
+        let status = response.status();
+        if !status.is_success() {
+            if sync_token.is_some() && is_invalid_sync_token(status, response.body()) {
+                return Err(InvalidSyncToken.into());
+            }
+            anyhow::bail!("REPORT sync-collection failed with {status}");
+        }
+
+        let parsed = parse_multistatus_bytes(response.body())?;
+        Ok(map_sync_response(
+            response.headers(),
+            parsed.items,
+            parsed.sync_token,
+        ))
+    }
+
+    /// Apply the items of one sync-collection response
+    ///
+    /// Returns (`events_count`, `todos_count`, `journals_count`, `deleted_count`,
+    /// `failed_count`)
+    async fn apply_sync_items(
+        &self,
+        calendar_url: &str,
+        calendar_name: &str,
+        items: &[SyncItem],
+    ) -> (usize, usize, usize, usize, usize) {
+        let mut deleted_count = 0;
+
+        // Separate deleted items from changed items
+        let mut hrefs_to_fetch = Vec::new();
+
+        for item in items {
+            if item.is_deleted {
+                deleted_count += self.process_deleted_item(&item.href).await;
+            } else if !item.href.ends_with('/') && !is_insufficient_storage(item) {
+                // Skip calendar collections, collect .ics files to fetch
+                hrefs_to_fetch.push(item.href.clone());
+            }
+        }
+
+        if hrefs_to_fetch.is_empty() {
+            return (0, 0, 0, deleted_count, 0);
+        }
+
+        let (events, todos, journals, failed) = self
+            .batch_fetch_calendar_items(calendar_url, calendar_name, &hrefs_to_fetch)
+            .await;
+        (events, todos, journals, deleted_count, failed)
     }
 
     /// Batch fetch calendar items using calendar-multiget
     ///
-    /// Returns (`events_count`, `todos_count`)
+    /// Returns (`events_count`, `todos_count`, `journals_count`, `failed_count`),
+    /// where `failed_count` is the number of hrefs in batches that failed.
     async fn batch_fetch_calendar_items(
         &self,
         calendar_url: &str,
         calendar_name: &str,
         hrefs: &[String],
-    ) -> (usize, usize) {
+    ) -> (usize, usize, usize, usize) {
         info!(
             "Fetching {} changed items for {} in batches",
             hrefs.len(),
@@ -390,6 +742,8 @@ impl SyncManager {
 
         let mut added_events = 0;
         let mut added_todos = 0;
+        let mut added_journals = 0;
+        let mut failed = 0;
 
         // Batch fetch items using calendar-multiget
         for (batch_num, chunk) in hrefs.chunks(BATCH_SIZE).enumerate() {
@@ -409,7 +763,7 @@ impl SyncManager {
                 Ok(objects) => {
                     for obj in objects {
                         if let Some(ical_data) = obj.calendar_data {
-                            let (events, todos) = self
+                            let (events, todos, journals) = self
                                 .process_calendar_item(
                                     &ical_data,
                                     &obj.href,
@@ -420,6 +774,7 @@ impl SyncManager {
                                 .await;
                             added_events += events;
                             added_todos += todos;
+                            added_journals += journals;
                         }
                     }
                 }
@@ -430,86 +785,297 @@ impl SyncManager {
                         calendar_name,
                         e
                     );
+                    failed += chunk.len();
                 }
             }
         }
 
-        (added_events, added_todos)
+        (added_events, added_todos, added_journals, failed)
     }
 
-    /// Perform full sync of a calendar
+    /// Sync a calendar by comparing the `ETag` of every resource
+    ///
+    /// Lists the hrefs and `ETags` in the calendar, fetches only resources that
+    /// are new or whose `ETag` changed, and drops cached resources that are no
+    /// longer listed. A calendar with nothing cached yet gets a full sync.
+    /// Returns whether every resource was listed and every new or changed
+    /// one fetched.
     ///
     /// # Errors
     ///
-    /// Returns an error if the calendar query fails or the `CalDAV` server is unreachable.
-    async fn sync_calendar_full(&self, calendar_url: &str, calendar_name: &str) -> Result<()> {
-        debug!("Full sync for {}", calendar_name);
-
-        // Fetch all calendar objects
-        let (events, todos) = self
-            .fetch_and_parse_calendar(calendar_url, calendar_name)
-            .await?;
+    /// Returns an error if the resources can't be listed or the full sync fails.
+    async fn sync_calendar_etag_diff(
+        &self,
+        calendar_url: &str,
+        calendar_name: &str,
+    ) -> Result<bool> {
+        let cached: std::collections::HashMap<String, Option<String>> = self
+            .data
+            .read()
+            .await
+            .objects
+            .values()
+            .filter(|object| object.calendar_url == calendar_url)
+            .map(|object| (object.href.clone(), object.etag.clone()))
+            .collect();
+        if cached.is_empty() {
+            debug!("Nothing cached for {}, using full sync", calendar_name);
+            return self.sync_calendar_full(calendar_url, calendar_name).await;
+        }
 
-        debug!(
-            "Full sync for {}: fetched {} events and {} todos",
-            calendar_name,
-            events.len(),
-            todos.len()
-        );
+        let (listed, failed_components) = self.list_resource_etags(calendar_url).await?;
 
-        // Replace all items for this calendar
-        let mut data = self.data.write().await;
+        // Without an ETag there is nothing to compare, so fetch it again
+        let changed: Vec<String> = listed
+            .iter()
+            .filter(|(href, etag)| etag.is_none() || cached.get(href.as_str()) != Some(etag))
+            .map(|(href, _)| href.clone())
+            .collect();
+        let unchanged = listed.len() - changed.len();
 
-        // Remove old items from this calendar
-        data.events.retain(|e| e.calendar_url != calendar_url);
-        data.todos.retain(|t| t.calendar_url != calendar_url);
+        let (added_events, added_todos, added_journals, failed) = if changed.is_empty() {
+            (0, 0, 0, 0)
+        } else {
+            self.batch_fetch_calendar_items(calendar_url, calendar_name, &changed)
+                .await
+        };
 
-        // Add new items
-        data.events.extend(events);
-        data.todos.extend(todos);
+        let mut data = self.data.write().await;
 
+        // Resources of a kind that couldn't be listed may still exist
+        let unlisted = data.resource_hrefs(calendar_url, &failed_components);
+        let deleted_count: usize = cached
+            .keys()
+            .filter(|href| !listed.contains_key(href.as_str()) && !unlisted.contains(*href))
+            .map(|href| data.remove_resource(href))
+            .sum();
         drop(data);
 
-        Ok(())
-    }
+        info!(
+            "ETag sync for {}: +{} events, +{} todos, +{} journals, -{} deleted, {} failed ({} unchanged)",
+            calendar_name,
+            added_events,
+            added_todos,
+            added_journals,
+            deleted_count,
+            failed,
+            unchanged
+        );
 
-    /// Start a background sync task that runs periodically
-    pub async fn start_periodic_sync(self: Arc<Self>, interval_minutes: u64) {
-        let mut ticker = interval(Duration::from_secs(interval_minutes * 60));
+        Ok(failed == 0 && failed_components.is_empty())
+    }
 
-        loop {
-            ticker.tick().await;
-            info!("Running periodic sync");
+    /// List the href and `ETag` of every resource in a calendar
+    ///
+    /// With a bounded sync window only resources inside it are listed, with
+    /// one calendar-query per kind of component. The kinds whose query failed
+    /// are returned alongside, so the rest of the calendar is still synced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PROPFIND or every calendar-query fails or the
+    /// response can't be parsed.
+    async fn list_resource_etags(
+        &self,
+        calendar_url: &str,
+    ) -> Result<(
+        std::collections::HashMap<String, Option<String>>,
+        Vec<&'static str>,
+    )> {
+        if self.window.is_bounded() {
+            let now = Utc::now();
+            let components = self.components(calendar_url).await;
+            let mut listed = std::collections::HashMap::new();
+            let mut failed = Vec::new();
+            let mut last_error = None;
+            for &component in &components {
+                let (start, end) = self.window.time_range(component, now);
+                match self
+                    .client
+                    .calendar_query_timerange(
+                        calendar_url,
+                        component,
+                        start.as_deref(),
+                        end.as_deref(),
+                        false,
+                    )
+                    .await
+                {
+                    Ok(objects) => {
+                        listed.extend(objects.into_iter().map(|obj| (obj.href, obj.etag)));
+                    }
+                    Err(e) => {
+                        warn!("Failed to list {}s in {}: {:?}", component, calendar_url, e);
+                        failed.push(component);
+                        last_error = Some(e);
+                    }
+                }
+            }
 
-            if let Err(e) = self.sync().await {
-                error!("Periodic sync failed: {}", e);
+            if failed.len() == components.len()
+                && let Some(e) = last_error
+            {
+                return Err(e);
             }
+            return Ok((listed, failed));
+        }
+
+        let body = r#"
+<D:propfind xmlns:D="DAV:">
+  <D:prop>
+    <D:getetag/>
+    <D:resourcetype/>
+  </D:prop>
+</D:propfind>
+"#;
+
+        let response = self.client.propfind(calendar_url, Depth::One, body).await?;
+        if !response.status().is_success() {
+            anyhow::bail!("PROPFIND getetag failed with {}", response.status());
         }
+
+        // The calendar itself is listed too
+        let listed = parse_multistatus_bytes(response.body())?
+            .items
+            .into_iter()
+            .filter(|item| !item.is_collection && !item.href.ends_with('/'))
+            .map(|item| (item.href, item.etag))
+            .collect();
+        Ok((listed, Vec::new()))
     }
 
-    /// Process a deleted item by removing it from events and todos
-    async fn process_deleted_item(&self, href: &str) -> usize {
+    /// Perform full sync of a calendar
+    ///
+    /// Returns whether every kind of component could be queried.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the calendar query fails or the `CalDAV` server is unreachable.
+    async fn sync_calendar_full(&self, calendar_url: &str, calendar_name: &str) -> Result<bool> {
+        debug!("Full sync for {}", calendar_name);
+
+        // Fetch all calendar objects
+        let (items, objects, complete) = self
+            .fetch_and_parse_calendar(calendar_url, calendar_name)
+            .await?;
+
+        debug!(
+            "Full sync for {}: fetched {} events, {} todos and {} journals",
+            calendar_name,
+            items.events.len(),
+            items.todos.len(),
+            items.journals.len()
+        );
+
+        // Replace all items for this calendar
         let mut data = self.data.write().await;
 
-        // Remove from events
-        let initial_events = data.events.len();
-        data.events
-            .retain(|e| !href.ends_with(&format!("{}.ics", e.uid)));
-        let events_deleted = initial_events - data.events.len();
+        // Remove old items from this calendar
+        data.events.retain(|e| e.calendar_url != calendar_url);
+        data.todos.retain(|t| t.calendar_url != calendar_url);
+        data.journals.retain(|j| j.calendar_url != calendar_url);
+        data.objects.retain(|_, o| o.calendar_url != calendar_url);
 
-        // Remove from todos
-        let initial_todos = data.todos.len();
-        data.todos
-            .retain(|t| !href.ends_with(&format!("{}.ics", t.uid)));
-        let todos_deleted = initial_todos - data.todos.len();
+        // Add new items
+        data.events.extend(items.events);
+        data.todos.extend(items.todos);
+        data.journals.extend(items.journals);
+        data.objects
+            .extend(objects.into_iter().map(|o| (o.href.clone(), o)));
 
-        debug!("Deleted item: {}", href);
         drop(data);
 
-        events_deleted + todos_deleted
+        Ok(complete)
+    }
+
+    /// Discover the calendar user addresses of the principal
+    ///
+    /// Reads `calendar-user-address-set` (RFC 6638), which lists the addresses
+    /// (e.g. `mailto:` URIs) attendees use to refer to the user. Returns an
+    /// empty list if the server doesn't provide it.
+    async fn discover_user_addresses(&self, principal: &str) -> Vec<String> {
+        let body = r#"
+<D:propfind xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop>
+    <C:calendar-user-address-set/>
+  </D:prop>
+</D:propfind>
+"#;
+
+        match self.client.propfind(principal, Depth::Zero, body).await {
+            Ok(response) if response.status().is_success() => {
+                let addresses = parse_user_address_set(&String::from_utf8_lossy(response.body()));
+                debug!("Calendar user addresses: {:?}", addresses);
+                addresses
+            }
+            Ok(response) => {
+                debug!(
+                    "PROPFIND calendar-user-address-set failed with {}",
+                    response.status()
+                );
+                Vec::new()
+            }
+            Err(e) => {
+                warn!("Failed to discover calendar user addresses: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Discover the `CTag` (`CalendarServer` getctag) of every calendar in `home`
+    ///
+    /// Returns a map of calendar URL to `CTag`. Servers without `CTags` give an
+    /// empty map.
+    async fn discover_ctags(&self, home: &str) -> std::collections::HashMap<String, String> {
+        let body = r#"
+<D:propfind xmlns:D="DAV:" xmlns:CS="http://calendarserver.org/ns/">
+  <D:prop>
+    <CS:getctag/>
+  </D:prop>
+</D:propfind>
+"#;
+
+        match self.client.propfind(home, Depth::One, body).await {
+            Ok(response) if response.status().is_success() => {
+                let ctags = parse_ctags(&String::from_utf8_lossy(response.body()));
+                debug!("Calendar CTags: {:?}", ctags);
+                ctags
+            }
+            Ok(response) => {
+                debug!("PROPFIND getctag failed with {}", response.status());
+                std::collections::HashMap::new()
+            }
+            Err(e) => {
+                warn!("Failed to discover calendar CTags: {}", e);
+                std::collections::HashMap::new()
+            }
+        }
     }
 
-    /// Process a calendar item (parse and add/update events and todos)
+    /// Start a background sync task that runs periodically
+    pub async fn start_periodic_sync(self: Arc<Self>, interval_minutes: u64) {
+        let mut ticker = interval(Duration::from_secs(interval_minutes * 60));
+
+        loop {
+            ticker.tick().await;
+            info!("Running periodic sync");
+
+            if let Err(e) = self.sync().await {
+                error!("Periodic sync failed: {}", e);
+            }
+        }
+    }
+
+    /// Process a deleted item by removing everything fetched from its href
+    async fn process_deleted_item(&self, href: &str) -> usize {
+        let deleted = self.data.write().await.remove_resource(href);
+        debug!("Deleted item: {} ({} items)", href, deleted);
+        deleted
+    }
+
+    /// Process a calendar item (parse and add/update events, todos and journals)
+    ///
+    /// Returns (`events_count`, `todos_count`, `journals_count`)
     async fn process_calendar_item(
         &self,
         ical_data: &str,
@@ -517,79 +1083,60 @@ impl SyncManager {
         etag: Option<&String>,
         calendar_name: &str,
         calendar_url: &str,
-    ) -> (usize, usize) {
+    ) -> (usize, usize, usize) {
         debug!("Parsing iCalendar data for {}", href);
 
-        match ical_data.parse::<Calendar>() {
+        match parse_calendar(ical_data) {
             Ok(calendar) => {
-                let mut data = self.data.write().await;
-                let mut events_added = 0;
-                let mut todos_added = 0;
-
-                // Process events
-                for event_comp in calendar.events() {
-                    // Get calendar color
-                    let calendar_color = {
-                        let colors = self.calendar_colors.read().await;
-                        colors.get(calendar_url).cloned()
-                    };
-
-                    match parse_event(
-                        event_comp,
-                        calendar_name,
-                        calendar_url,
-                        calendar_color.as_deref(),
-                        etag.map(String::as_str),
-                    ) {
-                        Ok(event) => {
-                            // Remove old instances of this event (by UID)
-                            data.events.retain(|e| e.uid != event.uid);
-
-                            // Expand recurring events
-                            let config = RecurrenceConfig::default();
-
-                            let instances = expand_recurring_event(&event, &config);
-
-                            let instance_count = instances.len();
-                            for instance in instances {
-                                data.events.push(instance);
-                            }
+                // Get calendar color
+                let calendar_color = self.calendar_colors.read().await.get(calendar_url).cloned();
+
+                let parsed = parse_components(
+                    &calendar,
+                    href,
+                    calendar_name,
+                    calendar_url,
+                    calendar_color.as_deref(),
+                    etag.map(String::as_str),
+                );
+                let counts = (
+                    parsed.events.len(),
+                    parsed.todos.len(),
+                    parsed.journals.len(),
+                );
 
-                            events_added += instance_count;
-                        }
-                        Err(e) => warn!("Failed to parse event: {}", e),
-                    }
-                }
+                // Keep the original source of the resource
+                let object = calendar_object(
+                    href,
+                    calendar_url,
+                    etag.map(String::as_str),
+                    &calendar,
+                    ical_data,
+                );
 
-                // Process todos
-                for todo_comp in calendar.todos() {
-                    match parse_todo(
-                        todo_comp,
-                        calendar_name,
-                        calendar_url,
-                        etag.map(String::as_str),
-                    ) {
-                        Ok(todo) => {
-                            data.todos.retain(|t| t.uid != todo.uid);
-                            data.todos.push(todo);
-                            todos_added += 1;
-                        }
-                        Err(e) => warn!("Failed to parse todo: {}", e),
-                    }
-                }
+                // Everything fetched from this href is replaced together.
+                // Recurring events and todos are expanded at query time.
+                self.data.write().await.replace_resource(
+                    object,
+                    parsed.events,
+                    parsed.todos,
+                    parsed.journals,
+                );
 
-                drop(data);
-                (events_added, todos_added)
+                counts
             }
             Err(e) => {
                 warn!("Failed to parse iCalendar data from {}: {}", href, e);
                 debug!("iCalendar data that failed to parse: {}", ical_data);
-                (0, 0)
+                (0, 0, 0)
             }
         }
     }
 
-    /// Fetch calendar objects and parse them into events and todos
+    /// Fetch calendar objects and parse them into events, todos and journals
+    ///
+    /// The original source of every parsed resource is returned alongside,
+    /// followed by whether the query for every kind of component succeeded.
     ///
     /// # Errors
     ///
@@ -598,108 +1145,399 @@ impl SyncManager {
         &self,
         calendar_url: &str,
         calendar_name: &str,
-    ) -> Result<(Vec<CalendarEvent>, Vec<Todo>)> {
-        let mut events = Vec::new();
-        let mut todos = Vec::new();
+    ) -> Result<(ParsedItems, Vec<CalendarObject>, bool)> {
+        let mut items = ParsedItems::default();
+        let mut sources: Vec<CalendarObject> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut complete = true;
+
+        let calendar_color = self.calendar_colors.read().await.get(calendar_url).cloned();
+
+        let now = Utc::now();
+        for component in self.components(calendar_url).await {
+            debug!("Querying {}s from: {}", component, calendar_url);
+            let (start, end) = self.window.time_range(component, now);
+            let objects = match self
+                .client
+                .calendar_query_timerange(
+                    calendar_url,
+                    component,
+                    start.as_deref(),
+                    end.as_deref(),
+                    true,
+                )
+                .await
+            {
+                Ok(objects) => objects,
+                Err(e) => {
+                    // Only kinds the calendar offers are queried, so the
+                    // calendar is fetched again next sync
+                    warn!(
+                        "Failed to query {}s from {}: {:?}",
+                        component, calendar_url, e
+                    );
+                    complete = false;
+                    continue;
+                }
+            };
+            debug!(
+                "Fetched {} {}s from {}",
+                objects.len(),
+                component,
+                calendar_name
+            );
 
-        // Fetch VEVENTs (calendar events)
-        debug!("Querying VEVENTs from: {}", calendar_url);
-        match self
-            .client
-            .calendar_query_timerange(calendar_url, "VEVENT", None, None, true)
-            .await
-        {
-            Ok(objects) => {
-                debug!("Fetched {} VEVENTs from {}", objects.len(), calendar_name);
-                for obj in objects {
-                    if let Some(ical_data) = obj.calendar_data {
-                        let etag = obj.etag.clone();
-                        match ical_data.parse::<Calendar>() {
-                            Ok(calendar) => {
-                                for event_comp in calendar.events() {
-                                    // Get calendar color
-                                    let calendar_color = {
-                                        let colors = self.calendar_colors.read().await;
-                                        colors.get(calendar_url).cloned()
-                                    };
-
-                                    match parse_event(
-                                        event_comp,
-                                        calendar_name,
-                                        calendar_url,
-                                        calendar_color.as_deref(),
-                                        etag.as_deref(),
-                                    ) {
-                                        Ok(event) => {
-                                            // Expand recurring events
-                                            let config = RecurrenceConfig::default();
-                                            let instances = expand_recurring_event(&event, &config);
-                                            for instance in instances {
-                                                events.push(instance);
-                                            }
-                                        }
-                                        Err(e) => {
-                                            warn!("Failed to parse event: {}", e);
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Failed to parse iCalendar data from {}: {}", obj.href, e);
-                            }
-                        }
+            for obj in objects {
+                // A resource holds one kind of component, but a server that
+                // ignores the filter returns it for every query
+                if !seen.insert(obj.href.clone()) {
+                    continue;
+                }
+                let Some(ical_data) = obj.calendar_data else {
+                    continue;
+                };
+
+                match parse_calendar(&ical_data) {
+                    Ok(calendar) => {
+                        items.extend(parse_components(
+                            &calendar,
+                            &obj.href,
+                            calendar_name,
+                            calendar_url,
+                            calendar_color.as_deref(),
+                            obj.etag.as_deref(),
+                        ));
+                        sources.push(calendar_object(
+                            &obj.href,
+                            calendar_url,
+                            obj.etag.as_deref(),
+                            &calendar,
+                            &ical_data,
+                        ));
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse iCalendar data from {}: {}", obj.href, e);
                     }
                 }
             }
-            Err(e) => {
-                debug!("Failed to query VEVENTs from {}: {:?}", calendar_url, e);
-                // Not an error - calendar might not have events
-            }
         }
 
-        // Fetch VTODOs (tasks)
-        debug!("Querying VTODOs from: {}", calendar_url);
-        match self
-            .client
-            .calendar_query_timerange(calendar_url, "VTODO", None, None, true)
-            .await
+        Ok((items, sources, complete))
+    }
+}
+
+/// Events, todos and journal entries parsed from calendar object resources
+#[derive(Debug, Default)]
+struct ParsedItems {
+    events: Vec<CalendarEvent>,
+    todos: Vec<Todo>,
+    journals: Vec<Journal>,
+}
+
+impl ParsedItems {
+    /// Append the items of another resource
+    fn extend(&mut self, other: Self) {
+        self.events.extend(other.events);
+        self.todos.extend(other.todos);
+        self.journals.extend(other.journals);
+    }
+}
+
+/// Kinds of components that are synced
+const COMPONENTS: &[&str] = &["VEVENT", "VTODO", "VJOURNAL"];
+
+/// The synced kinds of components a calendar offers
+///
+/// Servers that don't report `supported-calendar-component-set` (RFC 4791)
+/// allow every kind.
+fn queried_components(supported: &[String]) -> Vec<&'static str> {
+    if supported.is_empty() {
+        return COMPONENTS.to_vec();
+    }
+
+    COMPONENTS
+        .iter()
+        .copied()
+        .filter(|component| {
+            supported
+                .iter()
+                .any(|offered| offered.eq_ignore_ascii_case(component))
+        })
+        .collect()
+}
+
+/// Whether a failed sync-collection REPORT rejected the sync token
+///
+/// RFC 6578 servers answer with a `DAV:valid-sync-token` precondition error
+/// (usually 403 or 409); some answer an expired token with 410 Gone.
+fn is_invalid_sync_token(status: hyper::StatusCode, body: &[u8]) -> bool {
+    status == hyper::StatusCode::GONE || String::from_utf8_lossy(body).contains("valid-sync-token")
+}
+
+/// Whether a sync-collection item reports 507 Insufficient Storage
+///
+/// RFC 6578 servers mark a truncated result this way on the request-URI.
+fn is_insufficient_storage(item: &SyncItem) -> bool {
+    item.status
+        .as_deref()
+        .is_some_and(|status| status.contains("507"))
+}
+
+/// Whether a sync-collection response holds only part of the changes
+///
+/// Besides the RFC 6578 507 marker, a page filled up to the requested limit
+/// is treated as truncated, as some servers cut the result off silently.
+fn is_truncated(response: &SyncResponse) -> bool {
+    let changes = response
+        .items
+        .iter()
+        .filter(|item| !is_insufficient_storage(item))
+        .count();
+    changes < response.items.len()
+        || u32::try_from(changes).is_ok_and(|changes| changes >= SYNC_PAGE_LIMIT)
+}
+
+/// Properties that may occur several times in a component, but that
+/// icalendar keeps only the last of
+const REPEATED_PROPERTIES: &[&str] = &["RELATED-TO", "CONFERENCE"];
+
+/// Parse iCalendar text, keeping every occurrence of [`REPEATED_PROPERTIES`]
+///
+/// They are stored as multi-properties of their component, in the order
+/// they appear.
+///
+/// # Errors
+///
+/// Returns an error if the text isn't valid iCalendar.
+fn parse_calendar(ics: &str) -> Result<Calendar, String> {
+    let unfolded = icalendar::parser::unfold(ics);
+    let parsed = icalendar::parser::read_calendar(&unfolded)?;
+
+    // Components are converted in order, so they can be matched up by position
+    let repeated: Vec<Vec<Property>> = parsed
+        .components
+        .iter()
+        .map(|component| {
+            component
+                .properties
+                .iter()
+                .filter(|property| REPEATED_PROPERTIES.contains(&property.name.as_str()))
+                .map(|property| Property::from(property.clone()))
+                .collect()
+        })
+        .collect();
+
+    let mut calendar = Calendar::from(parsed);
+    for (component, properties) in calendar.components.iter_mut().zip(repeated) {
+        match component {
+            CalendarComponent::Event(event) => restore_repeated(event, properties),
+            CalendarComponent::Todo(todo) => restore_repeated(todo, properties),
+            CalendarComponent::Other(other) => restore_repeated(other, properties),
+            _ => {}
+        }
+    }
+
+    Ok(calendar)
+}
+
+/// Replace the last-one-wins copies of repeated properties with all of them
+fn restore_repeated(component: &mut impl Component, properties: Vec<Property>) {
+    if properties.is_empty() {
+        return;
+    }
+    for name in REPEATED_PROPERTIES {
+        component.remove_property(name);
+    }
+    for property in properties {
+        component.append_multi_property(property);
+    }
+}
+
+/// Parse the events, todos and journal entries of a calendar object resource
+///
+/// Every item records `href` so it can be updated and deleted together with
+/// the resource.
+fn parse_components(
+    calendar: &Calendar,
+    href: &str,
+    calendar_name: &str,
+    calendar_url: &str,
+    calendar_color: Option<&str>,
+    etag: Option<&str>,
+) -> ParsedItems {
+    // TZIDs may refer to VTIMEZONEs defined in this object
+    let timezones = TimezoneRegistry::from_calendar(calendar);
+    let mut items = ParsedItems::default();
+
+    for event_comp in calendar.events() {
+        match parse_event(
+            event_comp,
+            calendar_name,
+            calendar_url,
+            calendar_color,
+            etag,
+            &timezones,
+        ) {
+            Ok(event) => items.events.push(CalendarEvent {
+                href: Some(href.to_string()),
+                ..event
+            }),
+            Err(e) => warn!("Failed to parse event: {}", e),
+        }
+    }
+
+    for todo_comp in calendar.todos() {
+        match parse_todo(todo_comp, calendar_name, calendar_url, etag, &timezones) {
+            Ok(todo) => items.todos.push(Todo {
+                href: Some(href.to_string()),
+                ..todo
+            }),
+            Err(e) => warn!("Failed to parse todo: {}", e),
+        }
+    }
+
+    // icalendar has no dedicated type for VJOURNAL
+    let journal_comps = calendar
+        .components
+        .iter()
+        .filter_map(|component| match component {
+            CalendarComponent::Other(other) if other.component_kind() == "VJOURNAL" => Some(other),
+            _ => None,
+        });
+    for journal_comp in journal_comps {
+        match parse_journal(journal_comp, calendar_name, calendar_url, etag, &timezones) {
+            Ok(journal) => items.journals.push(Journal {
+                href: Some(href.to_string()),
+                ..journal
+            }),
+            Err(e) => warn!("Failed to parse journal: {}", e),
+        }
+    }
+
+    items
+}
+
+/// Keep the original source of a calendar object resource
+fn calendar_object(
+    href: &str,
+    calendar_url: &str,
+    etag: Option<&str>,
+    calendar: &Calendar,
+    ics: &str,
+) -> CalendarObject {
+    let mut uids: Vec<String> = Vec::new();
+    for component in &calendar.components {
+        let uid = match component {
+            CalendarComponent::Event(event) => event.get_uid(),
+            CalendarComponent::Todo(todo) => todo.get_uid(),
+            CalendarComponent::Other(other) => other.property_value("UID"),
+            _ => None,
+        };
+        if let Some(uid) = uid
+            && !uids.iter().any(|u| u == uid)
         {
-            Ok(objects) => {
-                debug!("Fetched {} VTODOs from {}", objects.len(), calendar_name);
-                for obj in objects {
-                    if let Some(ical_data) = obj.calendar_data {
-                        let etag = obj.etag.clone();
-                        match ical_data.parse::<Calendar>() {
-                            Ok(calendar) => {
-                                for todo_comp in calendar.todos() {
-                                    match parse_todo(
-                                        todo_comp,
-                                        calendar_name,
-                                        calendar_url,
-                                        etag.as_deref(),
-                                    ) {
-                                        Ok(todo) => todos.push(todo),
-                                        Err(e) => {
-                                            warn!("Failed to parse todo: {}", e);
-                                        }
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Failed to parse iCalendar data from {}: {}", obj.href, e);
-                            }
-                        }
+            uids.push(uid.to_string());
+        }
+    }
+
+    CalendarObject {
+        href: href.to_string(),
+        calendar_url: calendar_url.to_string(),
+        etag: etag.map(String::from),
+        uids,
+        ics: ics.to_string(),
+    }
+}
+
+/// Extract the `calendar-user-address-set` hrefs from a PROPFIND response
+fn parse_user_address_set(xml: &str) -> Vec<String> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut in_set = false;
+    let mut in_href = false;
+    let mut addresses = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(XmlEvent::Start(element)) => match element.local_name().as_ref() {
+                b"calendar-user-address-set" => in_set = true,
+                b"href" => in_href = in_set,
+                _ => {}
+            },
+            Ok(XmlEvent::End(element)) => match element.local_name().as_ref() {
+                b"calendar-user-address-set" => in_set = false,
+                b"href" => in_href = false,
+                _ => {}
+            },
+            Ok(XmlEvent::Text(text)) if in_href => {
+                if let Ok(value) = text.decode() {
+                    let value = value.trim();
+                    if !value.is_empty() {
+                        addresses.push(value.to_string());
                     }
                 }
             }
+            Ok(XmlEvent::Eof) => break,
             Err(e) => {
-                debug!("Failed to query VTODOs from {}: {:?}", calendar_url, e);
-                // Not an error - calendar might not have todos
+                debug!("Failed to parse calendar-user-address-set: {}", e);
+                break;
             }
+            _ => {}
         }
+    }
 
-        Ok((events, todos))
+    addresses
+}
+
+/// Parse the getctag of every response in a PROPFIND multistatus
+///
+/// Returns a map of href to `CTag`; responses without one are left out.
+fn parse_ctags(xml: &str) -> std::collections::HashMap<String, String> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut field: Option<&[u8]> = None;
+    let mut href = String::new();
+    let mut ctag = String::new();
+    let mut ctags = std::collections::HashMap::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(XmlEvent::Start(element)) => match element.local_name().as_ref() {
+                b"response" => {
+                    href.clear();
+                    ctag.clear();
+                }
+                b"href" => field = Some(b"href"),
+                b"getctag" => field = Some(b"getctag"),
+                _ => {}
+            },
+            Ok(XmlEvent::End(element)) => match element.local_name().as_ref() {
+                b"response" if !href.is_empty() && !ctag.is_empty() => {
+                    ctags.insert(href.clone(), ctag.clone());
+                }
+                b"href" | b"getctag" => field = None,
+                _ => {}
+            },
+            Ok(XmlEvent::Text(text)) => {
+                if let Some(field) = field
+                    && let Ok(value) = text.decode()
+                {
+                    let target = if field == b"href" {
+                        &mut href
+                    } else {
+                        &mut ctag
+                    };
+                    target.push_str(value.trim());
+                }
+            }
+            Ok(XmlEvent::Eof) => break,
+            Err(e) => {
+                debug!("Failed to parse getctag: {}", e);
+                break;
+            }
+            _ => {}
+        }
     }
+
+    ctags
 }
 
 /// Parse an iCalendar event component into a `CalendarEvent`
@@ -709,6 +1547,7 @@ fn parse_event(
     calendar_url: &str,
     calendar_color: Option<&str>,
     etag: Option<&str>,
+    timezones: &TimezoneRegistry,
 ) -> Result<CalendarEvent> {
     // UID is required
     let uid = event
@@ -727,30 +1566,81 @@ fn parse_event(
 
     // Start time (required)
     let start_opt = event.get_start();
-    let start = parse_datetime(start_opt.as_ref())
+    let start = parse_datetime(start_opt.as_ref(), timezones)
         .ok_or_else(|| anyhow::anyhow!("Event missing start time"))?;
 
-    // End time (use start + 1 hour if not specified)
-    let end = event.get_end().map_or_else(
-        || start + chrono::Duration::hours(1),
-        |end_time| {
-            parse_datetime(Some(&end_time)).unwrap_or_else(|| start + chrono::Duration::hours(1))
-        },
-    );
-
     // Check if all-day event (date without time)
     let all_day = matches!(start_opt.as_ref(), Some(DatePerhapsTime::Date(_)));
 
+    // End time: DTEND, then DURATION, then the RFC 5545 defaults (one day for
+    // all-day events, zero length for DATE-TIME events)
+    let end = parse_datetime(event.get_end().as_ref(), timezones)
+        .unwrap_or_else(|| default_event_end(event, &uid, start, all_day));
+
+    // All-day events keep their dates, which are the same in every timezone
+    let start_date = all_day.then(|| start.date_naive());
+    let end_date = all_day.then(|| end.date_naive());
+
+    // Keep the original zone of the start time so recurrences expand in it
+    let tzid = original_tzid(start_opt.as_ref());
+    let vtimezone = tzid.as_deref().and_then(|tzid| timezones.custom_zone(tzid));
+    let floating = matches!(
+        start_opt.as_ref(),
+        Some(DatePerhapsTime::DateTime(CalendarDateTime::Floating(_)))
+    );
+
     // Recurrence rule
     let rrule = event.property_value("RRULE").map(String::from);
 
     // Exception dates (EXDATE)
-    let exdates = parse_exdates(event);
-
-    // Status
-    let status = event.get_status().map(|s| format!("{s:?}"));
+    let (exdates, exdate_dates) = parse_exdates(event, timezones);
+
+    // Additional recurrence dates (RDATE)
+    let rdates = parse_rdates(event, timezones);
+
+    // Status (values outside RFC 5545 are ignored)
+    let status = event
+        .property_value("STATUS")
+        .and_then(|status| status.parse().ok());
+
+    // Recurrence ID (set when this VEVENT overrides one occurrence of a series)
+    let recurrence_id = event
+        .get_recurrence_id()
+        .and_then(|rid| parse_datetime(Some(&rid), timezones));
+
+    // Overrides and recurring masters belong to the series named by their UID
+    let instance_id = CalendarEvent::make_instance_id(&uid, recurrence_id);
+    let series_uid =
+        (recurrence_id.is_some() || rrule.is_some() || !rdates.is_empty()).then(|| uid.clone());
+
+    // Reminders (VALARM)
+    let alarms = parse_alarms(event, timezones);
+
+    // Organizer and participants
+    let organizer = event
+        .properties()
+        .get("ORGANIZER")
+        .map(|property| Organizer {
+            address: property.value().to_string(),
+            name: property_param(property, "CN"),
+        });
+    let attendees = parse_attendees(event);
+
+    // Descriptive properties
+    let url = event.get_url().map(String::from);
+    let conferences = parse_conferences(
+        event,
+        [
+            ("URL", url.as_deref()),
+            ("LOCATION", location.as_deref()),
+            ("DESCRIPTION", description.as_deref()),
+        ],
+    );
+    let class = upper_property(event, "CLASS");
+    let transparency = upper_property(event, "TRANSP");
+    let geo = event.property_value("GEO").and_then(parse_geo);
 
-    Ok(CalendarEvent {
+    let mut parsed = CalendarEvent {
         uid,
         summary,
         description,
@@ -761,32 +1651,155 @@ fn parse_event(
         calendar_url: calendar_url.to_string(),
         calendar_color: calendar_color.map(String::from),
         all_day,
+        start_date,
+        end_date,
+        tzid,
+        floating,
+        vtimezone,
         rrule,
         exdates,
+        exdate_dates,
+        rdates,
         status,
         etag: etag.map(String::from),
-    })
+        recurrence_id,
+        instance_id,
+        series_uid,
+        alarms,
+        organizer,
+        attendees,
+        // Filled in once the user's calendar addresses are known
+        own_partstat: None,
+        categories: parse_categories(event),
+        url,
+        class,
+        transparency,
+        sequence: event.get_sequence(),
+        created: event.get_created(),
+        last_modified: event.get_last_modified(),
+        geo,
+        priority: parse_priority(event),
+        conferences,
+        href: None,
+    };
+    parsed.resolve_alarms();
+
+    Ok(parsed)
 }
 
-/// Parse an iCalendar todo component into a `Todo`
-/// Parse EXDATE properties from an event
+/// Compute the end of an event that has no usable DTEND
 ///
-/// Returns a vector of `DateTime<Utc>` representing dates to exclude from recurrence
-fn parse_exdates(event: &Event) -> Vec<DateTime<Utc>> {
-    use icalendar::Component;
+/// Uses DURATION when present (negative durations are clamped to zero),
+/// otherwise one day for all-day events and zero length for DATE-TIME events.
+fn default_event_end(
+    event: &Event,
+    uid: &str,
+    start: DateTime<Utc>,
+    all_day: bool,
+) -> DateTime<Utc> {
+    match event.property_value("DURATION").and_then(parse_duration) {
+        Some(duration) if duration < chrono::Duration::zero() => {
+            warn!(
+                "Event {} has a negative DURATION, treating it as zero length",
+                uid
+            );
+            start
+        }
+        Some(duration) => start + duration,
+        None if all_day => start + chrono::Duration::days(1),
+        None => start,
+    }
+}
+
+/// Parse an iCalendar DURATION value (RFC 5545 section 3.3.6)
+///
+/// Accepts the week form (`P2W`), day and time forms (`P1DT2H30M`, `PT15M`)
+/// and a leading sign (`-PT15M`). Returns `None` for malformed values.
+pub(crate) fn parse_duration(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    let (negative, rest) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let rest = rest.strip_prefix('P').or_else(|| rest.strip_prefix('p'))?;
+
+    let mut total = chrono::Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut saw_component = false;
+
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            digit @ '0'..='9' => number.push(digit),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                let component = match (unit, in_time) {
+                    ('W', false) => chrono::Duration::try_weeks(amount)?,
+                    ('D', false) => chrono::Duration::try_days(amount)?,
+                    ('H', true) => chrono::Duration::try_hours(amount)?,
+                    ('M', true) => chrono::Duration::try_minutes(amount)?,
+                    ('S', true) => chrono::Duration::try_seconds(amount)?,
+                    _ => return None,
+                };
+                total = total.checked_add(&component)?;
+                saw_component = true;
+            }
+        }
+    }
+
+    if !number.is_empty() || !saw_component {
+        return None;
+    }
 
+    Some(if negative { -total } else { total })
+}
+
+/// Parse EXDATE properties from an event
+///
+/// Each value is read with the TZID and VALUE parameters of its property.
+/// DATE-TIME values are returned as UTC instants and DATE values (VALUE=DATE)
+/// as dates, which exclude every occurrence on that day. Floating values are
+/// local times of the series, so they are resolved in the zone of DTSTART.
+fn parse_exdates(
+    event: &Event,
+    timezones: &TimezoneRegistry,
+) -> (Vec<DateTime<Utc>>, Vec<NaiveDate>) {
     let mut exdates = Vec::new();
+    let mut exdate_dates = Vec::new();
+    let start = event.get_start();
 
     // Get all EXDATE properties (there can be multiple EXDATE lines)
     if let Some(exdate_props) = event.multi_properties().get("EXDATE") {
         for property in exdate_props {
-            let value = property.value();
             // EXDATE can be a comma-separated list or a single value
-            for date_str in value.split(',') {
+            for date_str in property.value().split(',') {
                 let trimmed = date_str.trim();
+                let single = single_value_property(property, trimmed);
+
+                // Fall back to the raw value for forms icalendar can't read
+                let date_time = match DatePerhapsTime::from_property(&single) {
+                    Some(DatePerhapsTime::Date(date)) => {
+                        exdate_dates.push(date);
+                        continue;
+                    }
+                    Some(DatePerhapsTime::DateTime(date_time)) => Some(date_time),
+                    None => parse_exdate_value(trimmed, timezones),
+                };
+
+                let exdate = match date_time {
+                    Some(CalendarDateTime::Floating(local)) => {
+                        in_start_zone(&local, start.as_ref(), timezones)
+                    }
+                    Some(date_time) => {
+                        parse_datetime(Some(&DatePerhapsTime::DateTime(date_time)), timezones)
+                    }
+                    None => None,
+                };
 
-                // Try to parse as datetime with timezone info
-                if let Some(dt) = parse_exdate_value(trimmed) {
+                if let Some(dt) = exdate {
                     exdates.push(dt);
                 } else {
                     debug!("Failed to parse EXDATE value: {}", trimmed);
@@ -795,17 +1808,101 @@ fn parse_exdates(event: &Event) -> Vec<DateTime<Utc>> {
         }
     }
 
-    exdates
+    (exdates, exdate_dates)
+}
+
+/// Parse RDATE properties from an event
+///
+/// Supports DATE, DATE-TIME (with or without TZID) and PERIOD values. For a
+/// PERIOD only the start is used; the instance keeps the series duration.
+fn parse_rdates(event: &Event, timezones: &TimezoneRegistry) -> Vec<DateTime<Utc>> {
+    let mut rdates = Vec::new();
+
+    if let Some(rdate_props) = event.multi_properties().get("RDATE") {
+        for property in rdate_props {
+            // RDATE can be a comma-separated list or a single value
+            for value in property.value().split(',') {
+                let trimmed = value.trim();
+
+                // A PERIOD is "start/end" or "start/duration"
+                let start_value = trimmed.split('/').next().unwrap_or(trimmed);
+
+                if let Some(dt) = parse_rdate_value(property, start_value, timezones) {
+                    rdates.push(dt);
+                } else {
+                    debug!("Failed to parse RDATE value: {}", trimmed);
+                }
+            }
+        }
+    }
+
+    rdates
+}
+
+/// Parse a single RDATE value using the TZID and VALUE parameters of its property
+fn parse_rdate_value(
+    property: &Property,
+    value: &str,
+    timezones: &TimezoneRegistry,
+) -> Option<DateTime<Utc>> {
+    let single = single_value_property(property, value);
+    parse_datetime(DatePerhapsTime::from_property(&single).as_ref(), timezones)
+}
+
+/// Copy one value of a list property (EXDATE, RDATE) with its TZID and VALUE parameters
+fn single_value_property(property: &Property, value: &str) -> Property {
+    let mut single = Property::new(property.key(), value);
+
+    if let Some(tzid) = property.params().get("TZID") {
+        single.add_parameter("TZID", tzid.value());
+    }
+
+    if property.value_type() == Some(ValueType::Date) {
+        single.add_parameter("VALUE", "DATE");
+    }
+
+    single
 }
 
-/// Parse a single EXDATE value to `DateTime<Utc>`
-fn parse_exdate_value(value: &str) -> Option<DateTime<Utc>> {
+/// Resolve a floating local time in the zone of a series' start
+///
+/// Matches the zone the series is expanded in: the TZID of DTSTART, the
+/// local zone for a floating DTSTART and UTC for UTC and all-day starts.
+fn in_start_zone(
+    local: &NaiveDateTime,
+    start: Option<&DatePerhapsTime>,
+    timezones: &TimezoneRegistry,
+) -> Option<DateTime<Utc>> {
+    let date_time = match start {
+        Some(DatePerhapsTime::DateTime(CalendarDateTime::WithTimezone { tzid, .. })) => {
+            CalendarDateTime::WithTimezone {
+                date_time: *local,
+                tzid: tzid.clone(),
+            }
+        }
+        Some(DatePerhapsTime::DateTime(CalendarDateTime::Utc(_)) | DatePerhapsTime::Date(_)) => {
+            CalendarDateTime::Utc(Utc.from_utc_datetime(local))
+        }
+        Some(DatePerhapsTime::DateTime(CalendarDateTime::Floating(_))) | None => {
+            CalendarDateTime::Floating(*local)
+        }
+    };
+    parse_datetime(Some(&DatePerhapsTime::DateTime(date_time)), timezones)
+}
+
+/// Parse a single EXDATE value
+///
+/// A local time without a TZID is returned as floating, to be resolved in
+/// the zone of the series.
+fn parse_exdate_value(value: &str, timezones: &TimezoneRegistry) -> Option<CalendarDateTime> {
     // Format: YYYYMMDDTHHMMSSZ or YYYYMMDD
 
-    // Remove any timezone prefix (e.g., "TZID=America/New_York:")
-    let clean_value = value
-        .find(':')
-        .map_or(value, |colon_pos| &value[colon_pos + 1..]);
+    // Split off any timezone prefix (e.g., "TZID=America/New_York:")
+    let (tzid, clean_value) = value
+        .split_once(':')
+        .map_or((None, value), |(prefix, rest)| {
+            (prefix.strip_prefix("TZID="), rest)
+        });
 
     // Try to parse as UTC datetime (YYYYMMDDTHHmmssZ)
     if clean_value.ends_with('Z') && clean_value.len() == 16 {
@@ -822,7 +1919,8 @@ fn parse_exdate_value(value: &str) -> Option<DateTime<Utc>> {
         ) {
             return Utc
                 .with_ymd_and_hms(year, month, day, hour, min, sec)
-                .single();
+                .single()
+                .map(CalendarDateTime::Utc);
         }
     }
 
@@ -835,36 +1933,33 @@ fn parse_exdate_value(value: &str) -> Option<DateTime<Utc>> {
             clean_value[6..8].parse::<u32>(),
         )
     {
-        return Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).single();
+        return Utc
+            .with_ymd_and_hms(year, month, day, 0, 0, 0)
+            .single()
+            .map(CalendarDateTime::Utc);
     }
 
-    // Try to parse as datetime without Z (YYYYMMDDTHHmmss)
-    if clean_value.contains('T') && clean_value.len() == 15 {
-        let date_part = &clean_value[0..8];
-        let time_part = &clean_value[9..15];
-
-        if let (Ok(year), Ok(month), Ok(day), Ok(hour), Ok(min), Ok(sec)) = (
-            date_part[0..4].parse::<i32>(),
-            date_part[4..6].parse::<u32>(),
-            date_part[6..8].parse::<u32>(),
-            time_part[0..2].parse::<u32>(),
-            time_part[2..4].parse::<u32>(),
-            time_part[4..6].parse::<u32>(),
-        ) {
-            return Utc
-                .with_ymd_and_hms(year, month, day, hour, min, sec)
-                .single();
-        }
+    // Local datetime in a named zone (YYYYMMDDTHHmmss)
+    if let Some(tzid) = tzid
+        && let Ok(naive) = NaiveDateTime::parse_from_str(clean_value, "%Y%m%dT%H%M%S")
+        && let Some(result) = timezones.to_utc(tzid, &naive)
+    {
+        return Some(CalendarDateTime::Utc(result));
     }
 
-    None
+    // Floating datetime (YYYYMMDDTHHmmss)
+    NaiveDateTime::parse_from_str(clean_value, "%Y%m%dT%H%M%S")
+        .ok()
+        .map(CalendarDateTime::Floating)
 }
 
+/// Parse an iCalendar todo component into a `Todo`
 fn parse_todo(
     todo: &IcalTodo,
     calendar_name: &str,
     calendar_url: &str,
     etag: Option<&str>,
+    timezones: &TimezoneRegistry,
 ) -> Result<Todo> {
     // UID is required
     let uid = todo
@@ -879,22 +1974,33 @@ fn parse_todo(
     let description = todo.get_description().map(String::from);
 
     // Due date
-    let due = todo.get_due().and_then(|d| parse_datetime(Some(&d)));
+    let due_opt = todo.get_due();
+    let due = parse_datetime(due_opt.as_ref(), timezones);
 
     // Start date
-    let start = todo.get_start().and_then(|s| parse_datetime(Some(&s)));
+    let start_opt = todo.get_start();
+    let start = parse_datetime(start_opt.as_ref(), timezones);
+
+    // Keep date-only values and the original zone alongside the UTC times
+    let due_date = match due_opt {
+        Some(DatePerhapsTime::Date(date)) => Some(date),
+        _ => None,
+    };
+    let start_date = match start_opt {
+        Some(DatePerhapsTime::Date(date)) => Some(date),
+        _ => None,
+    };
+    let tzid = original_tzid(due_opt.as_ref()).or_else(|| original_tzid(start_opt.as_ref()));
+    let floating = matches!(
+        due_opt.as_ref().or(start_opt.as_ref()),
+        Some(DatePerhapsTime::DateTime(CalendarDateTime::Floating(_)))
+    );
 
     // Completed date
     let completed = todo.get_completed();
 
     // Priority (1-9, where 1 is highest)
-    let priority = todo.get_priority().and_then(|p| {
-        if (1..=9).contains(&p) {
-            u8::try_from(p).ok()
-        } else {
-            None
-        }
-    });
+    let priority = parse_priority(todo);
 
     // Percent complete (0-100)
     let percent_complete = todo.get_percent_complete().and_then(|p| {
@@ -905,12 +2011,25 @@ fn parse_todo(
         }
     });
 
-    // Status (default to NEEDS-ACTION if not specified)
+    // Status (default to NEEDS-ACTION if not specified or unknown)
     let status = todo
-        .get_status()
-        .map_or_else(|| "NEEDS-ACTION".to_string(), |s| format!("{s:?}"));
+        .property_value("STATUS")
+        .and_then(|status| status.parse().ok())
+        .unwrap_or_default();
+
+    // Recurrence rule and, for overridden occurrences, the original due date
+    let rrule = todo.property_value("RRULE").map(String::from);
+    let recurrence_id = todo
+        .get_recurrence_id()
+        .and_then(|rid| parse_datetime(Some(&rid), timezones));
+
+    // Reminders (VALARM)
+    let alarms = parse_alarms(todo, timezones);
 
-    Ok(Todo {
+    // Subtask links (RELATED-TO)
+    let (parent_uid, child_uids) = parse_related_to(todo);
+
+    let mut parsed = Todo {
         uid,
         summary,
         description,
@@ -923,9 +2042,351 @@ fn parse_todo(
         calendar_name: calendar_name.to_string(),
         calendar_url: calendar_url.to_string(),
         etag: etag.map(String::from),
+        rrule,
+        recurrence_id,
+        tzid,
+        floating,
+        due_date,
+        start_date,
+        alarms,
+        categories: parse_categories(todo),
+        url: todo.get_url().map(String::from),
+        class: upper_property(todo, "CLASS"),
+        sequence: todo.get_sequence(),
+        created: todo.get_created(),
+        last_modified: todo.get_last_modified(),
+        parent_uid,
+        child_uids,
+        href: None,
+    };
+    parsed.resolve_alarms();
+
+    Ok(parsed)
+}
+
+/// Parse an iCalendar VJOURNAL component into a `Journal`
+fn parse_journal(
+    journal: &impl Component,
+    calendar_name: &str,
+    calendar_url: &str,
+    etag: Option<&str>,
+    timezones: &TimezoneRegistry,
+) -> Result<Journal> {
+    // UID is required
+    let uid = journal
+        .get_uid()
+        .ok_or_else(|| anyhow::anyhow!("Journal missing UID"))?
+        .to_string();
+
+    // Summary (title)
+    let summary = journal.get_summary().unwrap_or("Untitled Note").to_string();
+
+    // Date the entry belongs to, keeping date-only values
+    let start_opt = journal.get_start();
+    let start = parse_datetime(start_opt.as_ref(), timezones);
+    let start_date = match start_opt {
+        Some(DatePerhapsTime::Date(date)) => Some(date),
+        _ => None,
+    };
+
+    Ok(Journal {
+        uid,
+        summary,
+        description: journal.get_description().map(String::from),
+        start,
+        status: journal
+            .property_value("STATUS")
+            .and_then(|status| status.parse().ok()),
+        calendar_name: calendar_name.to_string(),
+        calendar_url: calendar_url.to_string(),
+        etag: etag.map(String::from),
+        tzid: original_tzid(start_opt.as_ref()),
+        start_date,
+        categories: parse_categories(journal),
+        url: journal.get_url().map(String::from),
+        class: upper_property(journal, "CLASS"),
+        created: journal.get_created(),
+        last_modified: journal.get_last_modified(),
+        href: None,
     })
 }
 
+/// Parse the ATTENDEE properties of an event
+///
+/// Missing parameters get their RFC 5545 defaults (NEEDS-ACTION,
+/// REQ-PARTICIPANT, INDIVIDUAL, no RSVP).
+fn parse_attendees(event: &Event) -> Vec<Attendee> {
+    event
+        .multi_properties()
+        .get("ATTENDEE")
+        .into_iter()
+        .flatten()
+        .map(|property| Attendee {
+            address: property.value().to_string(),
+            name: property_param(property, "CN"),
+            partstat: property_param(property, "PARTSTAT")
+                .map_or_else(|| "NEEDS-ACTION".to_string(), |p| p.to_ascii_uppercase()),
+            role: property_param(property, "ROLE")
+                .map_or_else(|| "REQ-PARTICIPANT".to_string(), |r| r.to_ascii_uppercase()),
+            rsvp: property_param(property, "RSVP").is_some_and(|r| r.eq_ignore_ascii_case("TRUE")),
+            cutype: property_param(property, "CUTYPE")
+                .map_or_else(|| "INDIVIDUAL".to_string(), |c| c.to_ascii_uppercase()),
+        })
+        .collect()
+}
+
+/// Parse the RELATED-TO links of a component into its parent and children
+///
+/// RELTYPE defaults to PARENT; SIBLING and other relations are ignored.
+/// The first PARENT link wins if there are several; icalendar only keeps
+/// all of them for components read with [`parse_calendar`].
+fn parse_related_to(component: &impl Component) -> (Option<String>, Vec<String>) {
+    let mut parent = None;
+    let mut children: Vec<String> = Vec::new();
+
+    let properties = component
+        .multi_properties()
+        .get("RELATED-TO")
+        .into_iter()
+        .flatten()
+        .chain(component.properties().get("RELATED-TO"));
+    for property in properties {
+        let uid = property.value().trim();
+        if uid.is_empty() {
+            continue;
+        }
+
+        let reltype = property_param(property, "RELTYPE")
+            .filter(|reltype| !reltype.is_empty())
+            .map_or_else(|| "PARENT".to_string(), |r| r.to_ascii_uppercase());
+        match reltype.as_str() {
+            "PARENT" if parent.is_none() => parent = Some(uid.to_string()),
+            "CHILD" if !children.iter().any(|c| c == uid) => children.push(uid.to_string()),
+            _ => {}
+        }
+    }
+
+    (parent, children)
+}
+
+/// Parse PRIORITY, keeping only the defined range (1-9, where 1 is highest)
+fn parse_priority(component: &impl Component) -> Option<u8> {
+    component
+        .get_priority()
+        .filter(|p| (1..=9).contains(p))
+        .and_then(|p| u8::try_from(p).ok())
+}
+
+/// Get an enumerated property value (CLASS, TRANSP) in upper case
+fn upper_property(component: &impl Component, name: &str) -> Option<String> {
+    component
+        .property_value(name)
+        .map(|value| value.trim().to_ascii_uppercase())
+        .filter(|value| !value.is_empty())
+}
+
+/// Collect the CATEGORIES of a component
+///
+/// CATEGORIES may appear several times and each value is a comma-separated
+/// list, so all of them are flattened into one list without duplicates.
+fn parse_categories(component: &impl Component) -> Vec<String> {
+    let mut categories: Vec<String> = Vec::new();
+    let values = component
+        .multi_properties()
+        .get("CATEGORIES")
+        .into_iter()
+        .flatten()
+        .map(Property::value)
+        .chain(component.property_value("CATEGORIES"));
+
+    for value in values {
+        for category in value.split(',') {
+            let category = category.trim().replace("\\,", ",");
+            if !category.is_empty() && !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+    }
+
+    categories
+}
+
+/// Parse a GEO value ("latitude;longitude")
+fn parse_geo(value: &str) -> Option<Geo> {
+    let (latitude, longitude) = value.split_once(';')?;
+    let latitude: f64 = latitude.trim().parse().ok()?;
+    let longitude: f64 = longitude.trim().parse().ok()?;
+
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then_some(Geo {
+        latitude,
+        longitude,
+    })
+}
+
+/// Hosts of online meeting services whose join links are picked out of free text
+const MEETING_HOSTS: &[&str] = &[
+    "zoom.us",
+    "zoomgov.com",
+    "teams.microsoft.com",
+    "teams.live.com",
+    "meet.google.com",
+    "webex.com",
+    "gotomeeting.com",
+    "whereby.com",
+    "meet.jit.si",
+];
+
+/// Collect the online meetings of an event
+///
+/// The CONFERENCE property (RFC 7986) and Google's X-GOOGLE-CONFERENCE come
+/// first, followed by meeting links found in the given text properties.
+/// The same URI is only reported once.
+///
+/// icalendar only keeps every CONFERENCE for events read with
+/// [`parse_calendar`].
+fn parse_conferences<'a>(
+    event: &Event,
+    texts: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+) -> Vec<Conference> {
+    let mut conferences: Vec<Conference> = Vec::new();
+    let mut push = |conference: Conference| {
+        if !conferences.iter().any(|c| c.uri == conference.uri) {
+            conferences.push(conference);
+        }
+    };
+
+    let conference_properties = event
+        .multi_properties()
+        .get("CONFERENCE")
+        .into_iter()
+        .flatten()
+        .chain(event.properties().get("CONFERENCE"));
+    for property in conference_properties {
+        let uri = property.value().trim();
+        if !uri.is_empty() {
+            push(Conference {
+                uri: uri.to_string(),
+                label: property_param(property, "LABEL"),
+                source: "CONFERENCE".to_string(),
+            });
+        }
+    }
+
+    if let Some(uri) = event.property_value("X-GOOGLE-CONFERENCE") {
+        let uri = uri.trim();
+        if !uri.is_empty() {
+            push(Conference {
+                uri: uri.to_string(),
+                label: None,
+                source: "X-GOOGLE-CONFERENCE".to_string(),
+            });
+        }
+    }
+
+    for (source, text) in texts {
+        for uri in text.map(find_meeting_links).unwrap_or_default() {
+            push(Conference {
+                uri,
+                label: None,
+                source: source.to_string(),
+            });
+        }
+    }
+
+    conferences
+}
+
+/// Find links to known online meeting services in free text
+fn find_meeting_links(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '(' | ')'))
+        .filter_map(|word| {
+            let start = word.find("https://")?;
+            let link = word[start..].trim_end_matches(['.', ',', ';', ']', '\\']);
+            let host = link["https://".len()..]
+                .split(['/', '?', '#'])
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            MEETING_HOSTS
+                .iter()
+                .any(|known| host == *known || host.ends_with(&format!(".{known}")))
+                .then(|| link.to_string())
+        })
+        .collect()
+}
+
+/// Get a property parameter value without surrounding quotes
+fn property_param(property: &Property, name: &str) -> Option<String> {
+    property
+        .params()
+        .get(name)
+        .map(|param| param.value().trim_matches('"').to_string())
+}
+
+/// Parse the VALARM sub-components of an event or todo
+///
+/// Relative triggers keep their offset so they can be resolved for every
+/// occurrence; absolute triggers (VALUE=DATE-TIME) are used as is. Alarms
+/// without a usable TRIGGER are skipped.
+fn parse_alarms(component: &impl Component, timezones: &TimezoneRegistry) -> Vec<Alarm> {
+    component
+        .components()
+        .iter()
+        .filter(|child| child.component_kind().eq_ignore_ascii_case("VALARM"))
+        .filter_map(|alarm| {
+            let Some(trigger) = alarm.properties().get("TRIGGER") else {
+                debug!("Skipping VALARM without TRIGGER");
+                return None;
+            };
+
+            let absolute = trigger
+                .params()
+                .get("VALUE")
+                .is_some_and(|value| value.value().eq_ignore_ascii_case("DATE-TIME"));
+
+            let (trigger_offset_seconds, trigger_related, trigger_at) = if absolute {
+                let Some(at) =
+                    parse_datetime(DatePerhapsTime::from_property(trigger).as_ref(), timezones)
+                else {
+                    debug!("Failed to parse VALARM TRIGGER: {}", trigger.value());
+                    return None;
+                };
+                (None, None, Some(at))
+            } else {
+                let Some(offset) = parse_duration(trigger.value()) else {
+                    debug!("Failed to parse VALARM TRIGGER: {}", trigger.value());
+                    return None;
+                };
+                let related = trigger
+                    .params()
+                    .get("RELATED")
+                    .map_or_else(|| "START".to_string(), |r| r.value().to_ascii_uppercase());
+                (Some(offset.num_seconds()), Some(related), None)
+            };
+
+            Some(Alarm {
+                action: alarm
+                    .property_value("ACTION")
+                    .map_or_else(|| "DISPLAY".to_string(), str::to_ascii_uppercase),
+                description: alarm.property_value("DESCRIPTION").map(String::from),
+                trigger_offset_seconds,
+                trigger_related,
+                trigger_at,
+            })
+        })
+        .collect()
+}
+
+/// Get the TZID a date-time was specified in (if any)
+fn original_tzid(date_time: Option<&DatePerhapsTime>) -> Option<String> {
+    match date_time? {
+        DatePerhapsTime::DateTime(CalendarDateTime::WithTimezone { tzid, .. }) => {
+            Some(tzid.clone())
+        }
+        _ => None,
+    }
+}
+
 /// Parse a `DatePerhapsTime` into a UTC `DateTime`
 ///
 /// All timezones are normalized to UTC for consistent storage and querying.
@@ -935,7 +2396,10 @@ fn parse_todo(
 /// - Date-only values (all-day events) use midnight UTC
 ///
 /// The consumer can convert to their preferred timezone when displaying.
-fn parse_datetime(date_time: Option<&DatePerhapsTime>) -> Option<DateTime<Utc>> {
+fn parse_datetime(
+    date_time: Option<&DatePerhapsTime>,
+    timezones: &TimezoneRegistry,
+) -> Option<DateTime<Utc>> {
     match date_time? {
         DatePerhapsTime::DateTime(CalendarDateTime::Utc(dt)) => {
             // Already in UTC
@@ -973,6 +2437,13 @@ fn parse_datetime(date_time: Option<&DatePerhapsTime>) -> Option<DateTime<Utc>>
                     date_time, tzid, result
                 );
                 Some(result)
+            } else if let Some(result) = timezones.to_utc(tzid, date_time) {
+                // Windows zone names, vendor-prefixed IDs and VTIMEZONE definitions
+                debug!(
+                    "Parsed datetime with resolved timezone: {} {} -> {} UTC",
+                    date_time, tzid, result
+                );
+                Some(result)
             } else {
                 // If timezone parsing fails, log a warning and treat as UTC
                 warn!("Failed to parse timezone '{}', treating as UTC", tzid);
@@ -1029,7 +2500,8 @@ fn parse_gmt_offset(tzid: &str) -> Option<chrono::FixedOffset> {
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
-    use chrono::{Datelike, NaiveDateTime, Timelike};
+    use crate::models::{EventStatus, JournalStatus, TodoStatus};
+    use chrono::{Datelike, Timelike};
 
     #[test]
     fn test_parse_datetime_with_date() {
@@ -1037,7 +2509,7 @@ mod tests {
         let date = chrono::NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
         let dpt = DatePerhapsTime::Date(date);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         let dt = result.unwrap();
@@ -1054,7 +2526,7 @@ mod tests {
         let cal_dt = CalendarDateTime::Utc(dt);
         let dpt = DatePerhapsTime::DateTime(cal_dt);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         let parsed = result.unwrap();
@@ -1072,7 +2544,7 @@ mod tests {
         let cal_dt = CalendarDateTime::Floating(naive);
         let dpt = DatePerhapsTime::DateTime(cal_dt);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         let parsed = result.unwrap();
@@ -1088,7 +2560,7 @@ mod tests {
 
     #[test]
     fn test_parse_datetime_none() {
-        let result = parse_datetime(None);
+        let result = parse_datetime(None, &TimezoneRegistry::default());
         assert!(result.is_none());
     }
 
@@ -1105,7 +2577,14 @@ mod tests {
             .ends(Utc.with_ymd_and_hms(2026, 3, 15, 11, 0, 0).unwrap())
             .done();
 
-        let result = parse_event(&event, "Test Calendar", "/calendar/test", None, None);
+        let result = parse_event(
+            &event,
+            "Test Calendar",
+            "/calendar/test",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1130,7 +2609,14 @@ mod tests {
             .ends(Utc.with_ymd_and_hms(2026, 4, 1, 10, 0, 0).unwrap())
             .done();
 
-        let result = parse_event(&event, "Calendar", "/cal", None, Some("etag-123"));
+        let result = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            None,
+            Some("etag-123"),
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1148,7 +2634,14 @@ mod tests {
             .all_day(date)
             .done();
 
-        let result = parse_event(&event, "Calendar", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1165,7 +2658,14 @@ mod tests {
             .starts(Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap())
             .done();
 
-        let result = parse_event(&event, "Cal", "/c", None, None);
+        let result = parse_event(
+            &event,
+            "Cal",
+            "/c",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1187,7 +2687,7 @@ mod tests {
             .percent_complete(50)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1207,7 +2707,7 @@ mod tests {
 
         let todo = IcalTodo::new().uid("todo-min").done();
 
-        let result = parse_todo(&todo, "Tasks", "/t", None);
+        let result = parse_todo(&todo, "Tasks", "/t", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1218,7 +2718,7 @@ mod tests {
         assert_eq!(parsed.completed, None);
         assert_eq!(parsed.priority, None);
         assert_eq!(parsed.percent_complete, None);
-        assert_eq!(parsed.status, "NEEDS-ACTION");
+        assert_eq!(parsed.status, TodoStatus::NeedsAction);
     }
 
     #[test]
@@ -1233,7 +2733,13 @@ mod tests {
             .percent_complete(100)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", Some("etag-456"));
+        let result = parse_todo(
+            &todo,
+            "Tasks",
+            "/tasks",
+            Some("etag-456"),
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1274,13 +2780,13 @@ mod tests {
 
         // Valid priority (1-9)
         let todo = IcalTodo::new().uid("p1").priority(1).done();
-        let result = parse_todo(&todo, "T", "/t", None);
+        let result = parse_todo(&todo, "T", "/t", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
         assert_eq!(result.unwrap().priority, Some(1));
 
         // Priority 9 (edge case)
         let todo = IcalTodo::new().uid("p9").priority(9).done();
-        let result = parse_todo(&todo, "T", "/t", None);
+        let result = parse_todo(&todo, "T", "/t", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
         assert_eq!(result.unwrap().priority, Some(9));
     }
@@ -1291,19 +2797,19 @@ mod tests {
 
         // 0% complete
         let todo = IcalTodo::new().uid("pc0").percent_complete(0).done();
-        let result = parse_todo(&todo, "T", "/t", None);
+        let result = parse_todo(&todo, "T", "/t", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
         assert_eq!(result.unwrap().percent_complete, Some(0));
 
         // 100% complete
         let todo = IcalTodo::new().uid("pc100").percent_complete(100).done();
-        let result = parse_todo(&todo, "T", "/t", None);
+        let result = parse_todo(&todo, "T", "/t", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
         assert_eq!(result.unwrap().percent_complete, Some(100));
 
         // 50% complete
         let todo = IcalTodo::new().uid("pc50").percent_complete(50).done();
-        let result = parse_todo(&todo, "T", "/t", None);
+        let result = parse_todo(&todo, "T", "/t", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
         assert_eq!(result.unwrap().percent_complete, Some(50));
     }
@@ -1318,7 +2824,7 @@ mod tests {
         };
         let dpt = DatePerhapsTime::DateTime(cal_dt);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         let parsed = result.unwrap();
@@ -1340,7 +2846,7 @@ mod tests {
         };
         let dpt = DatePerhapsTime::DateTime(cal_dt);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         // Should fall back to treating as UTC
@@ -1358,7 +2864,7 @@ mod tests {
         };
         let dpt = DatePerhapsTime::DateTime(cal_dt);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         let parsed = result.unwrap();
@@ -1380,7 +2886,14 @@ mod tests {
             .ends(Utc.with_ymd_and_hms(2026, 3, 15, 11, 0, 0).unwrap())
             .done();
 
-        let result = parse_event(&event, "Calendar", "/cal", Some("#FF5733"), None);
+        let result = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            Some("#FF5733"),
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1399,13 +2912,90 @@ mod tests {
             .add_property("RRULE", "FREQ=WEEKLY;BYDAY=MO")
             .done();
 
-        let result = parse_event(&event, "Calendar", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
         assert_eq!(parsed.rrule, Some("FREQ=WEEKLY;BYDAY=MO".to_string()));
     }
 
+    #[test]
+    fn test_parse_event_with_recurrence_id() {
+        use icalendar::Event;
+
+        let event = Event::new()
+            .uid("recurring-event")
+            .summary("Weekly Meeting (moved)")
+            .starts(Utc.with_ymd_and_hms(2026, 3, 22, 14, 0, 0).unwrap())
+            .ends(Utc.with_ymd_and_hms(2026, 3, 22, 15, 0, 0).unwrap())
+            .add_property("RECURRENCE-ID", "20260322T100000Z")
+            .done();
+
+        let parsed = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.recurrence_id,
+            Some(Utc.with_ymd_and_hms(2026, 3, 22, 10, 0, 0).unwrap())
+        );
+        assert_eq!(parsed.instance_id, "recurring-event:20260322T100000Z");
+        assert_eq!(parsed.series_uid, Some("recurring-event".to_string()));
+    }
+
+    #[test]
+    fn test_parse_event_instance_identifiers() {
+        use icalendar::Event;
+
+        let single = Event::new()
+            .uid("single-event")
+            .summary("Single")
+            .starts(Utc.with_ymd_and_hms(2026, 3, 22, 14, 0, 0).unwrap())
+            .ends(Utc.with_ymd_and_hms(2026, 3, 22, 15, 0, 0).unwrap())
+            .done();
+        let parsed = parse_event(
+            &single,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(parsed.instance_id, "single-event");
+        assert_eq!(parsed.series_uid, None);
+
+        let master = Event::new()
+            .uid("series")
+            .summary("Series")
+            .starts(Utc.with_ymd_and_hms(2026, 3, 22, 14, 0, 0).unwrap())
+            .ends(Utc.with_ymd_and_hms(2026, 3, 22, 15, 0, 0).unwrap())
+            .add_property("RRULE", "FREQ=DAILY")
+            .done();
+        let parsed = parse_event(
+            &master,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(parsed.series_uid, Some("series".to_string()));
+    }
+
     #[test]
     fn test_parse_event_with_status() {
         use icalendar::Event;
@@ -1418,11 +3008,18 @@ mod tests {
             .status(icalendar::EventStatus::Confirmed)
             .done();
 
-        let result = parse_event(&event, "Calendar", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, Some("Confirmed".to_string()));
+        assert_eq!(parsed.status, Some(EventStatus::Confirmed));
     }
 
     #[test]
@@ -1435,19 +3032,290 @@ mod tests {
             .starts(Utc.with_ymd_and_hms(2026, 3, 15, 10, 0, 0).unwrap())
             .done();
 
-        let result = parse_event(&event, "Calendar", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        // Should default to 1 hour after start
+        // A DATE-TIME start without DTEND or DURATION has zero length
         assert_eq!(
             parsed.start,
             Utc.with_ymd_and_hms(2026, 3, 15, 10, 0, 0).unwrap()
         );
+        assert_eq!(parsed.end, parsed.start);
+    }
+
+    #[test]
+    fn test_parse_all_day_event_without_end_time() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:all-day-no-end
+SUMMARY:Holiday
+DTSTART;VALUE=DATE:20260315
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let event = calendar.events().next().unwrap();
+        let parsed = parse_event(
+            event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+
+        // An all-day start without DTEND or DURATION lasts one day
+        assert!(parsed.all_day);
+        assert_eq!(parsed.end - parsed.start, chrono::Duration::days(1));
+    }
+
+    #[test]
+    fn test_parse_all_day_event_keeps_dates() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:trip
+SUMMARY:Trip
+DTSTART;VALUE=DATE:20260305
+DTEND;VALUE=DATE:20260308
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let event = calendar.events().next().unwrap();
+        let parsed = parse_event(
+            event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+
+        assert_eq!(parsed.start_date, NaiveDate::from_ymd_opt(2026, 3, 5));
+        assert_eq!(parsed.end_date, NaiveDate::from_ymd_opt(2026, 3, 8));
+        assert_eq!(parsed.tzid, None);
+    }
+
+    #[test]
+    fn test_parse_todo_keeps_dates_and_zone() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTODO
+UID:date-only
+SUMMARY:Date only
+DUE;VALUE=DATE:20260305
+END:VTODO
+BEGIN:VTODO
+UID:zoned
+SUMMARY:Zoned
+DTSTART;TZID=America/Chicago:20260301T090000
+DUE;TZID=America/Chicago:20260305T170000
+END:VTODO
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let todos: Vec<_> = calendar
+            .todos()
+            .map(|t| parse_todo(t, "Tasks", "/tasks", None, &TimezoneRegistry::default()).unwrap())
+            .collect();
+
+        assert_eq!(todos[0].due_date, NaiveDate::from_ymd_opt(2026, 3, 5));
+        assert_eq!(todos[0].start_date, None);
+        assert_eq!(todos[0].tzid, None);
+
+        assert_eq!(todos[1].due_date, None);
+        assert_eq!(todos[1].tzid, Some("America/Chicago".to_string()));
+        assert_eq!(
+            todos[1].due,
+            Some(Utc.with_ymd_and_hms(2026, 3, 5, 23, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_event_with_non_iana_timezones() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTIMEZONE
+TZID:Custom Central
+BEGIN:STANDARD
+DTSTART:16011104T020000
+RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=11
+TZOFFSETFROM:-0500
+TZOFFSETTO:-0600
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:16010311T020000
+RRULE:FREQ=YEARLY;BYDAY=2SU;BYMONTH=3
+TZOFFSETFROM:-0600
+TZOFFSETTO:-0500
+END:DAYLIGHT
+END:VTIMEZONE
+BEGIN:VEVENT
+UID:exchange
+SUMMARY:Exchange meeting
+DTSTART;TZID=Pacific Standard Time:20260715T090000
+DTEND;TZID=Pacific Standard Time:20260715T100000
+END:VEVENT
+BEGIN:VEVENT
+UID:custom
+SUMMARY:Custom zone meeting
+DTSTART;TZID=Custom Central:20260115T090000
+DTEND;TZID=Custom Central:20260115T100000
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let timezones = TimezoneRegistry::from_calendar(&calendar);
+        let events: Vec<_> = calendar
+            .events()
+            .map(|e| parse_event(e, "Calendar", "/cal", None, None, &timezones).unwrap())
+            .collect();
+
+        // Windows zone name mapped to America/Los_Angeles (PDT, UTC-7)
+        assert_eq!(
+            events[0].start,
+            Utc.with_ymd_and_hms(2026, 7, 15, 16, 0, 0).unwrap()
+        );
+        assert_eq!(events[0].tzid, Some("Pacific Standard Time".to_string()));
+
+        // Custom VTIMEZONE, standard time in January (UTC-6)
+        assert_eq!(
+            events[1].start,
+            Utc.with_ymd_and_hms(2026, 1, 15, 15, 0, 0).unwrap()
+        );
+        assert_eq!(
+            events[1].end,
+            Utc.with_ymd_and_hms(2026, 1, 15, 16, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_exdate_value_with_tzid_prefix() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTIMEZONE
+TZID:Fixed Minus Three
+BEGIN:STANDARD
+DTSTART:19700101T000000
+TZOFFSETFROM:-0300
+TZOFFSETTO:-0300
+END:STANDARD
+END:VTIMEZONE
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let timezones = TimezoneRegistry::from_calendar(&calendar);
+
+        assert_eq!(
+            parse_exdate_value("TZID=Fixed Minus Three:20260115T090000", &timezones),
+            Some(CalendarDateTime::Utc(
+                Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap()
+            ))
+        );
+        assert_eq!(
+            parse_exdate_value("TZID=W. Europe Standard Time:20260115T090000", &timezones),
+            Some(CalendarDateTime::Utc(
+                Utc.with_ymd_and_hms(2026, 1, 15, 8, 0, 0).unwrap()
+            ))
+        );
+        assert_eq!(
+            parse_exdate_value("20260115T090000", &timezones),
+            Some(CalendarDateTime::Floating(
+                NaiveDate::from_ymd_opt(2026, 1, 15)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_event_with_duration() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:conference
+SUMMARY:Conference
+DTSTART:20260315T090000Z
+DURATION:P2DT8H
+END:VEVENT
+BEGIN:VEVENT
+UID:sprint
+SUMMARY:Sprint
+DTSTART;VALUE=DATE:20260316
+DURATION:P2W
+END:VEVENT
+BEGIN:VEVENT
+UID:backwards
+SUMMARY:Backwards
+DTSTART:20260315T090000Z
+DURATION:-PT15M
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let events: Vec<_> = calendar
+            .events()
+            .map(|e| {
+                parse_event(
+                    e,
+                    "Calendar",
+                    "/cal",
+                    None,
+                    None,
+                    &TimezoneRegistry::default(),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        assert_eq!(
+            events[0].end,
+            Utc.with_ymd_and_hms(2026, 3, 17, 17, 0, 0).unwrap()
+        );
+        assert_eq!(events[1].end - events[1].start, chrono::Duration::weeks(2));
+        // Negative durations are clamped to a zero-length event
+        assert_eq!(events[2].end, events[2].start);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("PT1H"), Some(chrono::Duration::hours(1)));
+        assert_eq!(parse_duration("P3W"), Some(chrono::Duration::weeks(3)));
+        assert_eq!(
+            parse_duration("P1DT2H30M15S"),
+            Some(
+                chrono::Duration::days(1)
+                    + chrono::Duration::hours(2)
+                    + chrono::Duration::minutes(30)
+                    + chrono::Duration::seconds(15)
+            )
+        );
         assert_eq!(
-            parsed.end,
-            Utc.with_ymd_and_hms(2026, 3, 15, 11, 0, 0).unwrap()
+            parse_duration("-PT15M"),
+            Some(chrono::Duration::minutes(-15))
         );
+        assert_eq!(parse_duration("+P1D"), Some(chrono::Duration::days(1)));
+        assert_eq!(parse_duration("P0D"), Some(chrono::Duration::zero()));
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("P1H"), None);
+        assert_eq!(parse_duration("PT1D"), None);
+        assert_eq!(parse_duration("P1"), None);
+        assert_eq!(parse_duration("1H"), None);
+        assert_eq!(parse_duration(""), None);
     }
 
     #[test]
@@ -1460,11 +3328,11 @@ mod tests {
             .status(icalendar::TodoStatus::Completed)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, "Completed");
+        assert_eq!(parsed.status, TodoStatus::Completed);
     }
 
     #[test]
@@ -1477,11 +3345,11 @@ mod tests {
             .status(icalendar::TodoStatus::InProcess)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, "InProcess");
+        assert_eq!(parsed.status, TodoStatus::InProcess);
     }
 
     #[test]
@@ -1495,7 +3363,7 @@ mod tests {
             .starts(start_time)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1548,6 +3416,7 @@ mod tests {
             "/calendars/work/",
             Some("#0000FF"),
             Some("etag-xyz"),
+            &TimezoneRegistry::default(),
         );
         assert!(result.is_ok());
 
@@ -1560,7 +3429,7 @@ mod tests {
         assert_eq!(parsed.calendar_url, "/calendars/work/");
         assert_eq!(parsed.calendar_color, Some("#0000FF".to_string()));
         assert_eq!(parsed.rrule, Some("FREQ=MONTHLY".to_string()));
-        assert_eq!(parsed.status, Some("Confirmed".to_string()));
+        assert_eq!(parsed.status, Some(EventStatus::Confirmed));
         assert_eq!(parsed.etag, Some("etag-xyz".to_string()));
         assert!(!parsed.all_day);
     }
@@ -1585,7 +3454,13 @@ mod tests {
             .status(icalendar::TodoStatus::Completed)
             .done();
 
-        let result = parse_todo(&todo, "My Tasks", "/calendars/tasks/", Some("etag-abc"));
+        let result = parse_todo(
+            &todo,
+            "My Tasks",
+            "/calendars/tasks/",
+            Some("etag-abc"),
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1597,7 +3472,7 @@ mod tests {
         assert_eq!(parsed.completed, Some(completed_time));
         assert_eq!(parsed.priority, Some(3));
         assert_eq!(parsed.percent_complete, Some(100));
-        assert_eq!(parsed.status, "Completed");
+        assert_eq!(parsed.status, TodoStatus::Completed);
         assert_eq!(parsed.calendar_name, "My Tasks");
         assert_eq!(parsed.calendar_url, "/calendars/tasks/");
         assert_eq!(parsed.etag, Some("etag-abc".to_string()));
@@ -1615,7 +3490,7 @@ mod tests {
         };
         let dpt = DatePerhapsTime::DateTime(cal_dt);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         let parsed = result.unwrap();
@@ -1670,7 +3545,14 @@ mod tests {
             .ends(end_time)
             .done();
 
-        let result = parse_event(&event, "Test", "/test", None, None);
+        let result = parse_event(
+            &event,
+            "Test",
+            "/test",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1683,7 +3565,7 @@ mod tests {
 
         let todo = IcalTodo::new().uid("no-summary").done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1703,7 +3585,14 @@ mod tests {
             .ends(end_time)
             .done();
 
-        let result = parse_event(&event, "Calendar", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1726,7 +3615,14 @@ mod tests {
             .ends(end_time)
             .done();
 
-        let result = parse_event(&event, "Work", "/work", None, None);
+        let result = parse_event(
+            &event,
+            "Work",
+            "/work",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1743,7 +3639,7 @@ mod tests {
             .description("Task description")
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1760,11 +3656,11 @@ mod tests {
             .status(icalendar::TodoStatus::Cancelled)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, "Cancelled");
+        assert_eq!(parsed.status, TodoStatus::Cancelled);
     }
 
     #[test]
@@ -1777,11 +3673,11 @@ mod tests {
             .status(icalendar::TodoStatus::InProcess)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, "InProcess");
+        assert_eq!(parsed.status, TodoStatus::InProcess);
     }
 
     #[test]
@@ -1797,11 +3693,188 @@ mod tests {
             .status(icalendar::EventStatus::Tentative)
             .done();
 
-        let result = parse_event(&event, "Cal", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Cal",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, Some("Tentative".to_string()));
+        assert_eq!(parsed.status, Some(EventStatus::Tentative));
+    }
+
+    #[test]
+    fn test_parse_event_with_zoned_and_date_exdates() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:berlin-weekly
+DTSTART;TZID=Europe/Berlin:20260105T100000
+DTEND;TZID=Europe/Berlin:20260105T110000
+SUMMARY:Berlin weekly
+RRULE:FREQ=WEEKLY;COUNT=4
+EXDATE;TZID=Europe/Berlin:20260112T100000,20260119T100000
+END:VEVENT
+BEGIN:VEVENT
+UID:all-day-weekly
+DTSTART;VALUE=DATE:20260105
+DTEND;VALUE=DATE:20260106
+SUMMARY:All-day weekly
+RRULE:FREQ=WEEKLY;COUNT=4
+EXDATE;VALUE=DATE:20260112
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let events: Vec<_> = calendar
+            .events()
+            .map(|e| {
+                parse_event(e, "Test", "/test", None, None, &TimezoneRegistry::default()).unwrap()
+            })
+            .collect();
+
+        // Local Berlin times (CET, UTC+1) are converted, not read as UTC
+        assert_eq!(
+            events[0].exdates,
+            vec![
+                Utc.with_ymd_and_hms(2026, 1, 12, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 1, 19, 9, 0, 0).unwrap(),
+            ]
+        );
+        assert!(events[0].exdate_dates.is_empty());
+
+        assert!(events[1].exdates.is_empty());
+        assert_eq!(
+            events[1].exdate_dates,
+            vec![chrono::NaiveDate::from_ymd_opt(2026, 1, 12).unwrap()]
+        );
+
+        // Both series lose their excluded occurrences when expanded
+        let range_start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        let berlin =
+            crate::recurrence::expand_recurring_event(&events[0], &[], range_start, range_end);
+        assert_eq!(berlin.len(), 2);
+        let all_day =
+            crate::recurrence::expand_recurring_event(&events[1], &[], range_start, range_end);
+        assert_eq!(all_day.len(), 3);
+    }
+
+    #[test]
+    fn test_expand_event_in_custom_vtimezone() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTIMEZONE
+TZID:Custom Pacific
+BEGIN:STANDARD
+DTSTART:16011104T020000
+RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=11
+TZOFFSETFROM:-0700
+TZOFFSETTO:-0800
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:16010311T020000
+RRULE:FREQ=YEARLY;BYDAY=2SU;BYMONTH=3
+TZOFFSETFROM:-0800
+TZOFFSETTO:-0700
+END:DAYLIGHT
+END:VTIMEZONE
+BEGIN:VEVENT
+UID:custom-weekly
+DTSTART;TZID=Custom Pacific:20260302T090000
+DTEND;TZID=Custom Pacific:20260302T100000
+SUMMARY:Custom weekly
+RRULE:FREQ=WEEKLY;COUNT=3
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let timezones = TimezoneRegistry::from_calendar(&calendar);
+        let event = parse_event(
+            calendar.events().next().unwrap(),
+            "Test",
+            "/test",
+            None,
+            None,
+            &timezones,
+        )
+        .unwrap();
+        assert!(event.vtimezone.is_some());
+
+        // The zone is kept in the cache
+        let cached: CalendarEvent =
+            serde_json::from_str(&serde_json::to_string(&event).unwrap()).unwrap();
+        assert_eq!(cached, event);
+
+        // DST starts on 2026-03-08, so later occurrences are at 16:00 UTC
+        let range_start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap();
+        let starts: Vec<_> =
+            crate::recurrence::expand_recurring_event(&cached, &[], range_start, range_end)
+                .iter()
+                .map(|instance| instance.start)
+                .collect();
+        assert_eq!(
+            starts,
+            vec![
+                Utc.with_ymd_and_hms(2026, 3, 2, 17, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 9, 16, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 16, 16, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_event_with_floating_exdates() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:berlin-weekly
+DTSTART;TZID=Europe/Berlin:20260105T100000
+DTEND;TZID=Europe/Berlin:20260105T110000
+SUMMARY:Berlin weekly
+RRULE:FREQ=WEEKLY;COUNT=4
+EXDATE:20260112T100000
+END:VEVENT
+BEGIN:VEVENT
+UID:utc-weekly
+DTSTART:20260105T100000Z
+DTEND:20260105T110000Z
+SUMMARY:UTC weekly
+RRULE:FREQ=WEEKLY;COUNT=4
+EXDATE:20260112T100000
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let events: Vec<_> = calendar
+            .events()
+            .map(|e| {
+                parse_event(e, "Test", "/test", None, None, &TimezoneRegistry::default()).unwrap()
+            })
+            .collect();
+
+        // An EXDATE without a zone is a local time of the series
+        assert_eq!(
+            events[0].exdates,
+            vec![Utc.with_ymd_and_hms(2026, 1, 12, 9, 0, 0).unwrap()]
+        );
+        assert_eq!(
+            events[1].exdates,
+            vec![Utc.with_ymd_and_hms(2026, 1, 12, 10, 0, 0).unwrap()]
+        );
+
+        let range_start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let range_end = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        for event in &events {
+            let occurrences =
+                crate::recurrence::expand_recurring_event(event, &[], range_start, range_end);
+            assert_eq!(occurrences.len(), 3);
+        }
     }
 
     #[test]
@@ -1809,30 +3882,151 @@ mod tests {
         let ical_str = r"BEGIN:VCALENDAR
 VERSION:2.0
 BEGIN:VEVENT
-UID:test-exdate
-DTSTART:20260101T100000Z
-DTEND:20260101T110000Z
-SUMMARY:Event with EXDATE
-RRULE:FREQ=WEEKLY;COUNT=4
-EXDATE:20260108T100000Z
+UID:test-exdate
+DTSTART:20260101T100000Z
+DTEND:20260101T110000Z
+SUMMARY:Event with EXDATE
+RRULE:FREQ=WEEKLY;COUNT=4
+EXDATE:20260108T100000Z
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let events: Vec<_> = calendar.events().collect();
+        assert_eq!(events.len(), 1);
+
+        let result = parse_event(
+            events[0],
+            "Test",
+            "/test",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
+        assert!(result.is_ok());
+
+        let event = result.unwrap();
+        assert_eq!(event.uid, "test-exdate");
+        assert_eq!(event.rrule, Some("FREQ=WEEKLY;COUNT=4".to_string()));
+        assert_eq!(event.exdates.len(), 1);
+
+        // Verify the EXDATE was parsed correctly
+        let expected_exdate = Utc.with_ymd_and_hms(2026, 1, 8, 10, 0, 0).unwrap();
+        assert_eq!(event.exdates[0], expected_exdate);
+    }
+
+    #[test]
+    fn test_parse_event_with_rdates() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:test-rdate
+DTSTART:20260101T100000Z
+DTEND:20260101T110000Z
+SUMMARY:Event with RDATE
+RRULE:FREQ=WEEKLY;COUNT=4
+RDATE:20260103T100000Z,20260104T100000Z
+RDATE;TZID=Europe/Berlin:20260110T120000
+RDATE;VALUE=DATE:20260120
+RDATE;VALUE=PERIOD:20260125T090000Z/PT2H
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let events: Vec<_> = calendar.events().collect();
+
+        let event = parse_event(
+            events[0],
+            "Test",
+            "/test",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            event.rdates,
+            vec![
+                Utc.with_ymd_and_hms(2026, 1, 3, 10, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 1, 4, 10, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 1, 10, 11, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 1, 20, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 1, 25, 9, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_event_without_rdates() {
+        let event = Event::new()
+            .uid("no-rdate")
+            .summary("Plain")
+            .starts(Utc.with_ymd_and_hms(2026, 5, 1, 10, 0, 0).unwrap())
+            .ends(Utc.with_ymd_and_hms(2026, 5, 1, 11, 0, 0).unwrap())
+            .done();
+
+        let parsed = parse_event(
+            &event,
+            "Cal",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert!(parsed.rdates.is_empty());
+    }
+
+    #[test]
+    fn test_parse_event_keeps_original_zone() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:zoned
+DTSTART;TZID=America/Chicago:20260302T090000
+DTEND;TZID=America/Chicago:20260302T100000
+SUMMARY:Zoned
+RRULE:FREQ=WEEKLY
+END:VEVENT
+BEGIN:VEVENT
+UID:floating
+DTSTART:20260302T090000
+DTEND:20260302T100000
+SUMMARY:Floating
 END:VEVENT
 END:VCALENDAR";
 
         let calendar = ical_str.parse::<Calendar>().unwrap();
         let events: Vec<_> = calendar.events().collect();
-        assert_eq!(events.len(), 1);
-
-        let result = parse_event(events[0], "Test", "/test", None, None);
-        assert!(result.is_ok());
 
-        let event = result.unwrap();
-        assert_eq!(event.uid, "test-exdate");
-        assert_eq!(event.rrule, Some("FREQ=WEEKLY;COUNT=4".to_string()));
-        assert_eq!(event.exdates.len(), 1);
+        let zoned = parse_event(
+            events[0],
+            "Test",
+            "/test",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(zoned.tzid, Some("America/Chicago".to_string()));
+        assert!(!zoned.floating);
+        assert_eq!(
+            zoned.start,
+            Utc.with_ymd_and_hms(2026, 3, 2, 15, 0, 0).unwrap()
+        );
 
-        // Verify the EXDATE was parsed correctly
-        let expected_exdate = Utc.with_ymd_and_hms(2026, 1, 8, 10, 0, 0).unwrap();
-        assert_eq!(event.exdates[0], expected_exdate);
+        let floating = parse_event(
+            events[1],
+            "Test",
+            "/test",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(floating.tzid, None);
+        assert!(floating.floating);
     }
 
     #[test]
@@ -1848,11 +4042,18 @@ END:VCALENDAR";
             .status(icalendar::EventStatus::Cancelled)
             .done();
 
-        let result = parse_event(&event, "Cal", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Cal",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, Some("Cancelled".to_string()));
+        assert_eq!(parsed.status, Some(EventStatus::Cancelled));
     }
 
     #[test]
@@ -1865,11 +4066,59 @@ END:VCALENDAR";
             .status(icalendar::TodoStatus::NeedsAction)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
-        assert_eq!(parsed.status, "NeedsAction");
+        assert_eq!(parsed.status, TodoStatus::NeedsAction);
+    }
+
+    #[test]
+    fn test_parse_todo_with_rrule() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTODO
+UID:rent
+SUMMARY:Pay rent
+DUE:20260101T170000Z
+RRULE:FREQ=MONTHLY
+END:VTODO
+BEGIN:VTODO
+UID:rent
+RECURRENCE-ID:20260201T170000Z
+SUMMARY:Pay rent
+DUE:20260201T170000Z
+STATUS:COMPLETED
+END:VTODO
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let todos: Vec<_> = calendar.todos().collect();
+
+        let master = parse_todo(
+            todos[0],
+            "Tasks",
+            "/tasks",
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(master.rrule, Some("FREQ=MONTHLY".to_string()));
+        assert_eq!(master.recurrence_id, None);
+
+        let completed = parse_todo(
+            todos[1],
+            "Tasks",
+            "/tasks",
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            completed.recurrence_id,
+            Some(Utc.with_ymd_and_hms(2026, 2, 1, 17, 0, 0).unwrap())
+        );
+        assert!(completed.is_completed());
     }
 
     #[test]
@@ -1891,7 +4140,14 @@ END:VCALENDAR";
             .ends(dpt_end)
             .done();
 
-        let result = parse_event(&event, "Cal", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Cal",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1908,7 +4164,13 @@ END:VCALENDAR";
             .summary("Task without dates")
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", Some("etag-xyz"));
+        let result = parse_todo(
+            &todo,
+            "Tasks",
+            "/tasks",
+            Some("etag-xyz"),
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1930,7 +4192,14 @@ END:VCALENDAR";
             .ends(end_time)
             .done();
 
-        let result = parse_event(&event, "Cal", "/cal", None, None);
+        let result = parse_event(
+            &event,
+            "Cal",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        );
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1947,7 +4216,7 @@ END:VCALENDAR";
             .priority(0)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1965,7 +4234,7 @@ END:VCALENDAR";
             .percent_complete(0)
             .done();
 
-        let result = parse_todo(&todo, "Tasks", "/tasks", None);
+        let result = parse_todo(&todo, "Tasks", "/tasks", None, &TimezoneRegistry::default());
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -1977,7 +4246,7 @@ END:VCALENDAR";
         let date = chrono::NaiveDate::from_ymd_opt(2026, 8, 20).unwrap();
         let dpt = DatePerhapsTime::Date(date);
 
-        let result = parse_datetime(Some(&dpt));
+        let result = parse_datetime(Some(&dpt), &TimezoneRegistry::default());
         assert!(result.is_some());
 
         let parsed = result.unwrap();
@@ -1986,5 +4255,688 @@ END:VCALENDAR";
         assert_eq!(parsed.second(), 0);
     }
 
+    #[test]
+    fn test_parse_event_alarms() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:alarm-event
+SUMMARY:Dentist
+DTSTART:20260315T140000Z
+DTEND:20260315T150000Z
+BEGIN:VALARM
+ACTION:DISPLAY
+DESCRIPTION:Dentist in 15 minutes
+TRIGGER:-PT15M
+END:VALARM
+BEGIN:VALARM
+ACTION:AUDIO
+TRIGGER;RELATED=END:PT5M
+END:VALARM
+BEGIN:VALARM
+ACTION:EMAIL
+TRIGGER;VALUE=DATE-TIME:20260314T090000Z
+END:VALARM
+BEGIN:VALARM
+ACTION:DISPLAY
+END:VALARM
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let event = calendar.events().next().unwrap();
+        let parsed = parse_event(
+            event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+
+        // The alarm without a TRIGGER is skipped
+        assert_eq!(parsed.alarms.len(), 3);
+
+        let display = &parsed.alarms[0];
+        assert_eq!(display.action, "DISPLAY");
+        assert_eq!(
+            display.description.as_deref(),
+            Some("Dentist in 15 minutes")
+        );
+        assert_eq!(display.trigger_offset_seconds, Some(-15 * 60));
+        assert_eq!(display.trigger_related.as_deref(), Some("START"));
+        assert_eq!(
+            display.trigger_at,
+            Some(Utc.with_ymd_and_hms(2026, 3, 15, 13, 45, 0).unwrap())
+        );
+
+        let audio = &parsed.alarms[1];
+        assert_eq!(audio.action, "AUDIO");
+        assert_eq!(audio.trigger_related.as_deref(), Some("END"));
+        assert_eq!(
+            audio.trigger_at,
+            Some(Utc.with_ymd_and_hms(2026, 3, 15, 15, 5, 0).unwrap())
+        );
+
+        let email = &parsed.alarms[2];
+        assert_eq!(email.trigger_offset_seconds, None);
+        assert_eq!(
+            email.trigger_at,
+            Some(Utc.with_ymd_and_hms(2026, 3, 14, 9, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_todo_alarm_relative_to_due() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTODO
+UID:alarm-todo
+SUMMARY:File taxes
+DUE:20260415T170000Z
+BEGIN:VALARM
+ACTION:DISPLAY
+DESCRIPTION:Taxes due tomorrow
+TRIGGER;RELATED=END:-P1D
+END:VALARM
+END:VTODO
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let todo = calendar.todos().next().unwrap();
+        let parsed =
+            parse_todo(todo, "Tasks", "/tasks", None, &TimezoneRegistry::default()).unwrap();
+
+        assert_eq!(parsed.alarms.len(), 1);
+        assert_eq!(
+            parsed.alarms[0].trigger_at,
+            Some(Utc.with_ymd_and_hms(2026, 4, 14, 17, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_event_attendees_and_organizer() {
+        let ical_str = r#"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:meeting
+SUMMARY:Planning
+DTSTART:20260315T140000Z
+DTEND:20260315T150000Z
+ORGANIZER;CN="Doe, Jane":mailto:jane@example.com
+ATTENDEE;CN="Doe, Jane";PARTSTAT=ACCEPTED;ROLE=CHAIR:mailto:jane@example.com
+ATTENDEE;PARTSTAT=TENTATIVE;ROLE=OPT-PARTICIPANT;RSVP=TRUE:mailto:bob@example.com
+ATTENDEE;CUTYPE=ROOM:mailto:room-a@example.com
+END:VEVENT
+END:VCALENDAR"#;
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let event = calendar.events().next().unwrap();
+        let parsed = parse_event(
+            event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.organizer,
+            Some(Organizer {
+                address: "mailto:jane@example.com".to_string(),
+                name: Some("Doe, Jane".to_string()),
+            })
+        );
+        assert_eq!(parsed.attendees.len(), 3);
+
+        let bob = &parsed.attendees[1];
+        assert_eq!(bob.address, "mailto:bob@example.com");
+        assert_eq!(bob.name, None);
+        assert_eq!(bob.partstat, "TENTATIVE");
+        assert_eq!(bob.role, "OPT-PARTICIPANT");
+        assert!(bob.rsvp);
+        assert_eq!(bob.cutype, "INDIVIDUAL");
+
+        // Missing parameters get their defaults
+        let room = &parsed.attendees[2];
+        assert_eq!(room.partstat, "NEEDS-ACTION");
+        assert_eq!(room.role, "REQ-PARTICIPANT");
+        assert!(!room.rsvp);
+        assert_eq!(room.cutype, "ROOM");
+
+        // The user's status is only known once their addresses are
+        assert_eq!(parsed.own_partstat, None);
+        assert_eq!(
+            parsed.partstat_for(&["MAILTO:bob@example.com".to_string()]),
+            Some("TENTATIVE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_event_common_properties() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:common
+SUMMARY:Offsite
+DTSTART:20260315T140000Z
+DTEND:20260315T150000Z
+CATEGORIES:Work,Planning
+CATEGORIES:Travel,work
+URL:https://example.com/offsite
+CLASS:confidential
+TRANSP:TRANSPARENT
+SEQUENCE:3
+CREATED:20260101T090000Z
+LAST-MODIFIED:20260210T120000Z
+GEO:37.386013;-122.082932
+PRIORITY:2
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let event = calendar.events().next().unwrap();
+        let parsed = parse_event(
+            event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.categories,
+            vec!["Work", "Planning", "Travel", "work"]
+        );
+        assert_eq!(parsed.url.as_deref(), Some("https://example.com/offsite"));
+        assert_eq!(parsed.class.as_deref(), Some("CONFIDENTIAL"));
+        assert_eq!(parsed.transparency.as_deref(), Some("TRANSPARENT"));
+        assert_eq!(parsed.sequence, Some(3));
+        assert_eq!(
+            parsed.created,
+            Some(Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parsed.last_modified,
+            Some(Utc.with_ymd_and_hms(2026, 2, 10, 12, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parsed.geo,
+            Some(Geo {
+                latitude: 37.386_013,
+                longitude: -122.082_932,
+            })
+        );
+        assert_eq!(parsed.priority, Some(2));
+        assert!(parsed.conferences.is_empty());
+    }
+
+    #[test]
+    fn test_parse_event_conferences() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:call
+SUMMARY:Sync
+DTSTART:20260315T140000Z
+DTEND:20260315T150000Z
+CONFERENCE;VALUE=URI;FEATURE=VIDEO;LABEL=Team room:https://meet.example.org/team
+X-GOOGLE-CONFERENCE:https://meet.google.com/abc-defg-hij
+LOCATION:Microsoft Teams Meeting
+DESCRIPTION:Join: https://us02web.zoom.us/j/123456789?pwd=abc. Backup <https
+ ://meet.google.com/abc-defg-hij> or see https://example.com/agenda
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let event = calendar.events().next().unwrap();
+        let parsed = parse_event(
+            event,
+            "Calendar",
+            "/cal",
+            None,
+            None,
+            &TimezoneRegistry::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.conferences,
+            vec![
+                Conference {
+                    uri: "https://meet.example.org/team".to_string(),
+                    label: Some("Team room".to_string()),
+                    source: "CONFERENCE".to_string(),
+                },
+                Conference {
+                    uri: "https://meet.google.com/abc-defg-hij".to_string(),
+                    label: None,
+                    source: "X-GOOGLE-CONFERENCE".to_string(),
+                },
+                Conference {
+                    uri: "https://us02web.zoom.us/j/123456789?pwd=abc".to_string(),
+                    label: None,
+                    source: "DESCRIPTION".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_event_several_conferences() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:call
+SUMMARY:Sync
+DTSTART:20260315T140000Z
+DTEND:20260315T150000Z
+CONFERENCE;VALUE=URI;FEATURE=VIDEO;LABEL=Video:https://meet.example.org/team
+CONFERENCE;VALUE=URI;FEATURE=PHONE;LABEL=Dial-in:tel:+1-555-0100,,1234
+END:VEVENT
+END:VCALENDAR";
+
+        let calendar = parse_calendar(ical_str).unwrap();
+        let items = parse_components(&calendar, "/cal/call.ics", "Calendar", "/cal", None, None);
+
+        let uris: Vec<(&str, Option<&str>)> = items.events[0]
+            .conferences
+            .iter()
+            .map(|c| (c.uri.as_str(), c.label.as_deref()))
+            .collect();
+        assert_eq!(
+            uris,
+            vec![
+                ("https://meet.example.org/team", Some("Video")),
+                ("tel:+1-555-0100,,1234", Some("Dial-in")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_todo_common_properties() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTODO
+UID:todo-common
+SUMMARY:File taxes
+CATEGORIES:Home, Finance
+URL:https://example.com/taxes
+CLASS:PRIVATE
+SEQUENCE:1
+LAST-MODIFIED:20260210T120000Z
+END:VTODO
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let todo = calendar.todos().next().unwrap();
+        let parsed =
+            parse_todo(todo, "Tasks", "/tasks", None, &TimezoneRegistry::default()).unwrap();
+
+        assert_eq!(parsed.categories, vec!["Home", "Finance"]);
+        assert_eq!(parsed.url.as_deref(), Some("https://example.com/taxes"));
+        assert_eq!(parsed.class.as_deref(), Some("PRIVATE"));
+        assert_eq!(parsed.sequence, Some(1));
+        assert_eq!(parsed.created, None);
+        assert_eq!(
+            parsed.last_modified,
+            Some(Utc.with_ymd_and_hms(2026, 2, 10, 12, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_todo_related_to() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTODO
+UID:subtask
+SUMMARY:Buy paint
+RELATED-TO:project
+END:VTODO
+BEGIN:VTODO
+UID:project
+SUMMARY:Paint the fence
+RELATED-TO;RELTYPE=CHILD:other-subtask
+END:VTODO
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let mut todos = calendar
+            .todos()
+            .map(|todo| parse_todo(todo, "Tasks", "/tasks", None, &TimezoneRegistry::default()))
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .into_iter();
+
+        // RELTYPE defaults to PARENT
+        let subtask = todos.next().unwrap();
+        assert_eq!(subtask.parent_uid.as_deref(), Some("project"));
+        assert!(subtask.child_uids.is_empty());
+
+        let project = todos.next().unwrap();
+        assert_eq!(project.parent_uid, None);
+        assert_eq!(project.child_uids, vec!["other-subtask"]);
+    }
+
+    #[test]
+    fn test_parse_todo_several_related_to() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTODO
+UID:middle
+SUMMARY:Middle
+RELATED-TO;RELTYPE=CHILD:c1
+RELATED-TO;RELTYPE=CHILD:c2
+RELATED-TO;RELTYPE=PARENT:root
+RELATED-TO:other-root
+END:VTODO
+END:VCALENDAR";
+
+        let calendar = parse_calendar(ical_str).unwrap();
+        let items = parse_components(
+            &calendar,
+            "/tasks/middle.ics",
+            "Tasks",
+            "/tasks",
+            None,
+            None,
+        );
+
+        let todo = &items.todos[0];
+        assert_eq!(todo.child_uids, vec!["c1", "c2"]);
+        assert_eq!(todo.parent_uid.as_deref(), Some("root"));
+    }
+
+    #[test]
+    fn test_parse_components_with_journal() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VJOURNAL
+UID:note-1
+DTSTART;TZID=Europe/Berlin:20260315T090000
+SUMMARY:Retro
+DESCRIPTION:Went well
+STATUS:draft
+CLASS:PRIVATE
+END:VJOURNAL
+BEGIN:VJOURNAL
+SUMMARY:No UID
+END:VJOURNAL
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let items = parse_components(
+            &calendar,
+            "/notes/entry.ics",
+            "Notes",
+            "/notes",
+            None,
+            Some("etag-j"),
+        );
+
+        assert!(items.events.is_empty());
+        assert!(items.todos.is_empty());
+        assert_eq!(items.journals.len(), 1);
+
+        let journal = &items.journals[0];
+        assert_eq!(journal.uid, "note-1");
+        assert_eq!(journal.summary, "Retro");
+        assert_eq!(journal.description.as_deref(), Some("Went well"));
+        assert_eq!(
+            journal.start,
+            Some(Utc.with_ymd_and_hms(2026, 3, 15, 8, 0, 0).unwrap())
+        );
+        assert_eq!(journal.tzid.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(journal.start_date, None);
+        assert_eq!(journal.status, Some(JournalStatus::Draft));
+        assert_eq!(journal.href.as_deref(), Some("/notes/entry.ics"));
+        assert_eq!(journal.class.as_deref(), Some("PRIVATE"));
+        assert_eq!(journal.etag.as_deref(), Some("etag-j"));
+    }
+
+    #[test]
+    fn test_parse_unknown_status() {
+        let ical_str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:event
+DTSTART:20260315T140000Z
+STATUS:X-POSTPONED
+END:VEVENT
+BEGIN:VTODO
+UID:todo
+STATUS:x-waiting
+END:VTODO
+BEGIN:VTODO
+UID:lower
+STATUS:in-process
+END:VTODO
+END:VCALENDAR";
+
+        let calendar = ical_str.parse::<Calendar>().unwrap();
+        let items = parse_components(&calendar, "/cal/a.ics", "Calendar", "/cal", None, None);
+
+        assert_eq!(items.events[0].status, None);
+        assert_eq!(items.todos[0].status, TodoStatus::NeedsAction);
+        assert_eq!(items.todos[1].status, TodoStatus::InProcess);
+    }
+
+    #[test]
+    fn test_parse_geo() {
+        assert_eq!(
+            parse_geo("-33.8;151.2"),
+            Some(Geo {
+                latitude: -33.8,
+                longitude: 151.2,
+            })
+        );
+        assert_eq!(parse_geo("91.0;0.0"), None);
+        assert_eq!(parse_geo("nowhere"), None);
+    }
+
+    #[test]
+    fn test_find_meeting_links() {
+        assert_eq!(
+            find_meeting_links(
+                "Teams: https://teams.microsoft.com/l/meetup-join/19%3a, Webex (https://acme.webex.com/meet/bob)."
+            ),
+            vec![
+                "https://teams.microsoft.com/l/meetup-join/19%3a",
+                "https://acme.webex.com/meet/bob",
+            ]
+        );
+        assert!(find_meeting_links("https://notzoom.us/j/1 https://example.com").is_empty());
+    }
+
+    #[test]
+    fn test_parse_user_address_set() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:response>
+    <D:href>/principals/user/</D:href>
+    <D:propstat>
+      <D:prop>
+        <C:calendar-user-address-set>
+          <D:href>mailto:user@example.com</D:href>
+          <D:href> urn:uuid:1234 </D:href>
+        </C:calendar-user-address-set>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>"#;
+
+        // The response href is not part of the address set
+        assert_eq!(
+            parse_user_address_set(xml),
+            vec!["mailto:user@example.com", "urn:uuid:1234"]
+        );
+        assert!(parse_user_address_set("<not-xml").is_empty());
+    }
+
+    #[test]
+    fn test_parse_ctags() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<D:multistatus xmlns:D="DAV:" xmlns:CS="http://calendarserver.org/ns/">
+  <D:response>
+    <D:href>/calendars/user/</D:href>
+    <D:propstat>
+      <D:prop><CS:getctag/></D:prop>
+      <D:status>HTTP/1.1 404 Not Found</D:status>
+    </D:propstat>
+  </D:response>
+  <D:response>
+    <D:href>/calendars/user/work/</D:href>
+    <D:propstat>
+      <D:prop><CS:getctag> "ctag-42" </CS:getctag></D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>"#;
+
+        let ctags = parse_ctags(xml);
+        assert_eq!(ctags.len(), 1);
+        assert_eq!(
+            ctags.get("/calendars/user/work/").map(String::as_str),
+            Some("\"ctag-42\"")
+        );
+        assert!(parse_ctags("<not-xml").is_empty());
+    }
+
+    #[test]
+    fn test_queried_components() {
+        assert_eq!(queried_components(&[]), COMPONENTS);
+        assert_eq!(
+            queried_components(&["vevent".to_string(), "VFREEBUSY".to_string()]),
+            ["VEVENT"]
+        );
+        assert_eq!(
+            queried_components(&["VTODO".to_string(), "VEVENT".to_string()]),
+            ["VEVENT", "VTODO"]
+        );
+    }
+
+    #[test]
+    fn test_sync_window_bounds() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 15, 30, 0).unwrap();
+        let window = SyncWindow {
+            past_days: Some(365),
+            future_days: Some(730),
+        };
+
+        let (start, end) = window.bounds(now);
+        assert_eq!(
+            start,
+            Some(Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            end,
+            Some(Utc.with_ymd_and_hms(2028, 3, 10, 0, 0, 0).unwrap())
+        );
+
+        assert_eq!(
+            window.time_range("VEVENT", now),
+            (
+                Some("20250310T000000Z".to_string()),
+                Some("20280310T000000Z".to_string())
+            )
+        );
+        assert_eq!(window.time_range("VJOURNAL", now), (None, None));
+
+        let unbounded = SyncWindow::default();
+        assert!(!unbounded.is_bounded());
+        assert_eq!(unbounded.bounds(now), (None, None));
+        assert_eq!(unbounded.time_range("VTODO", now), (None, None));
+    }
+
+    #[test]
+    fn test_sync_window_covers() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 15, 30, 0).unwrap();
+        let window = SyncWindow {
+            past_days: Some(7),
+            future_days: None,
+        };
+
+        let day = |d| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap();
+        assert!(window.covers(day(3), day(20), now));
+        assert!(!window.covers(day(2), day(4), now));
+        assert!(SyncWindow::default().covers(day(1), day(31), now));
+    }
+
+    #[test]
+    fn test_sync_window_change_tag() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 15, 30, 0).unwrap();
+        assert_eq!(SyncWindow::default().change_tag("ctag-1", now), "ctag-1");
+
+        let window = SyncWindow {
+            past_days: Some(1),
+            future_days: None,
+        };
+        assert_eq!(window.change_tag("ctag-1", now), "ctag-1 20260309T000000Z/");
+
+        // The window moves daily, and so does the tag
+        let tomorrow = now + chrono::Duration::days(1);
+        assert_ne!(
+            window.change_tag("ctag-1", now),
+            window.change_tag("ctag-1", tomorrow)
+        );
+    }
+
+    fn sync_item(href: &str, status: &str) -> SyncItem {
+        SyncItem {
+            href: href.to_string(),
+            etag: None,
+            calendar_data: None,
+            status: Some(status.to_string()),
+            is_deleted: false,
+        }
+    }
+
+    #[test]
+    fn test_is_invalid_sync_token() {
+        let body = br#"<?xml version="1.0" encoding="utf-8"?>
+<d:error xmlns:d="DAV:"><d:valid-sync-token/></d:error>"#;
+        assert!(is_invalid_sync_token(hyper::StatusCode::FORBIDDEN, body));
+        assert!(is_invalid_sync_token(hyper::StatusCode::GONE, b""));
+        assert!(!is_invalid_sync_token(hyper::StatusCode::FORBIDDEN, b""));
+        assert!(!is_invalid_sync_token(
+            hyper::StatusCode::INTERNAL_SERVER_ERROR,
+            b"oops"
+        ));
+    }
+
+    #[test]
+    fn test_is_truncated() {
+        let complete = SyncResponse {
+            sync_token: Some("token".to_string()),
+            items: vec![sync_item("/cal/a.ics", "HTTP/1.1 200 OK")],
+        };
+        assert!(!is_truncated(&complete));
+
+        // RFC 6578 marks the request-URI with 507
+        let marked = SyncResponse {
+            sync_token: Some("token".to_string()),
+            items: vec![
+                sync_item("/cal/a.ics", "HTTP/1.1 200 OK"),
+                sync_item("/cal/", "HTTP/1.1 507 Insufficient Storage"),
+            ],
+        };
+        assert!(is_truncated(&marked));
+
+        // A page filled up to the limit may have been cut off silently
+        let full = SyncResponse {
+            sync_token: Some("token".to_string()),
+            items: (0..SYNC_PAGE_LIMIT)
+                .map(|i| sync_item(&format!("/cal/{i}.ics"), "HTTP/1.1 200 OK"))
+                .collect(),
+        };
+        assert!(is_truncated(&full));
+    }
+
     // Full integration tests for sync manager are in the integration test suite
 }
@@ -0,0 +1,535 @@
+// Copyright (C) 2026 Fred Clausen
+// Use of this source code is governed by an MIT-style
+// license that can be found in the LICENSE file or at
+// https://opensource.org/licenses/MIT.
+
+use crate::recurrence::parse_rrule;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use icalendar::{Calendar, CalendarComponent, Component};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Windows timezone names mapped to IANA zones
+///
+/// Taken from the CLDR `windowsZones` table (territory "001"). Exchange and
+/// Outlook use these names as TZIDs.
+const WINDOWS_ZONES: &[(&str, &str)] = &[
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Aleutian Standard Time", "America/Adak"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Marquesas Standard Time", "Pacific/Marquesas"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("UTC-09", "Etc/GMT+9"),
+    ("Pacific Standard Time (Mexico)", "America/Tijuana"),
+    ("UTC-08", "Etc/GMT+8"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time (Mexico)", "America/Mazatlan"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Yukon Standard Time", "America/Whitehorse"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Easter Island Standard Time", "Pacific/Easter"),
+    ("Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Eastern Standard Time (Mexico)", "America/Cancun"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("Haiti Standard Time", "America/Port-au-Prince"),
+    ("Cuba Standard Time", "America/Havana"),
+    ("US Eastern Standard Time", "America/Indiana/Indianapolis"),
+    ("Turks And Caicos Standard Time", "America/Grand_Turk"),
+    ("Paraguay Standard Time", "America/Asuncion"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("Venezuela Standard Time", "America/Caracas"),
+    ("Central Brazilian Standard Time", "America/Cuiaba"),
+    ("SA Western Standard Time", "America/La_Paz"),
+    ("Pacific SA Standard Time", "America/Santiago"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("Tocantins Standard Time", "America/Araguaina"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("SA Eastern Standard Time", "America/Cayenne"),
+    ("Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+    ("Greenland Standard Time", "America/Nuuk"),
+    ("Montevideo Standard Time", "America/Montevideo"),
+    ("Magallanes Standard Time", "America/Punta_Arenas"),
+    ("Saint Pierre Standard Time", "America/Miquelon"),
+    ("Bahia Standard Time", "America/Bahia"),
+    ("UTC-02", "Etc/GMT+2"),
+    ("Azores Standard Time", "Atlantic/Azores"),
+    ("Cape Verde Standard Time", "Atlantic/Cape_Verde"),
+    ("UTC", "Etc/UTC"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("Sao Tome Standard Time", "Africa/Sao_Tome"),
+    ("Morocco Standard Time", "Africa/Casablanca"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("Jordan Standard Time", "Asia/Amman"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("Middle East Standard Time", "Asia/Beirut"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("E. Europe Standard Time", "Europe/Chisinau"),
+    ("Syria Standard Time", "Asia/Damascus"),
+    ("West Bank Standard Time", "Asia/Hebron"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("FLE Standard Time", "Europe/Kyiv"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("South Sudan Standard Time", "Africa/Juba"),
+    ("Kaliningrad Standard Time", "Europe/Kaliningrad"),
+    ("Sudan Standard Time", "Africa/Khartoum"),
+    ("Libya Standard Time", "Africa/Tripoli"),
+    ("Namibia Standard Time", "Africa/Windhoek"),
+    ("Arabic Standard Time", "Asia/Baghdad"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Arab Standard Time", "Asia/Riyadh"),
+    ("Belarus Standard Time", "Europe/Minsk"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("E. Africa Standard Time", "Africa/Nairobi"),
+    ("Volgograd Standard Time", "Europe/Volgograd"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Astrakhan Standard Time", "Europe/Astrakhan"),
+    ("Azerbaijan Standard Time", "Asia/Baku"),
+    ("Russia Time Zone 3", "Europe/Samara"),
+    ("Mauritius Standard Time", "Indian/Mauritius"),
+    ("Saratov Standard Time", "Europe/Saratov"),
+    ("Georgian Standard Time", "Asia/Tbilisi"),
+    ("Caucasus Standard Time", "Asia/Yerevan"),
+    ("Afghanistan Standard Time", "Asia/Kabul"),
+    ("West Asia Standard Time", "Asia/Tashkent"),
+    ("Ekaterinburg Standard Time", "Asia/Yekaterinburg"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("Qyzylorda Standard Time", "Asia/Qyzylorda"),
+    ("India Standard Time", "Asia/Kolkata"),
+    ("Sri Lanka Standard Time", "Asia/Colombo"),
+    ("Nepal Standard Time", "Asia/Kathmandu"),
+    ("Central Asia Standard Time", "Asia/Almaty"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("Omsk Standard Time", "Asia/Omsk"),
+    ("Myanmar Standard Time", "Asia/Yangon"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("Altai Standard Time", "Asia/Barnaul"),
+    ("W. Mongolia Standard Time", "Asia/Hovd"),
+    ("North Asia Standard Time", "Asia/Krasnoyarsk"),
+    ("N. Central Asia Standard Time", "Asia/Novosibirsk"),
+    ("Tomsk Standard Time", "Asia/Tomsk"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("North Asia East Standard Time", "Asia/Irkutsk"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("Ulaanbaatar Standard Time", "Asia/Ulaanbaatar"),
+    ("Aus Central W. Standard Time", "Australia/Eucla"),
+    ("Transbaikal Standard Time", "Asia/Chita"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("North Korea Standard Time", "Asia/Pyongyang"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("Yakutsk Standard Time", "Asia/Yakutsk"),
+    ("Cen. Australia Standard Time", "Australia/Adelaide"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("West Pacific Standard Time", "Pacific/Port_Moresby"),
+    ("Tasmania Standard Time", "Australia/Hobart"),
+    ("Vladivostok Standard Time", "Asia/Vladivostok"),
+    ("Lord Howe Standard Time", "Australia/Lord_Howe"),
+    ("Bougainville Standard Time", "Pacific/Bougainville"),
+    ("Russia Time Zone 10", "Asia/Srednekolymsk"),
+    ("Magadan Standard Time", "Asia/Magadan"),
+    ("Norfolk Standard Time", "Pacific/Norfolk"),
+    ("Sakhalin Standard Time", "Asia/Sakhalin"),
+    ("Central Pacific Standard Time", "Pacific/Guadalcanal"),
+    ("Russia Time Zone 11", "Asia/Kamchatka"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+    ("UTC+12", "Etc/GMT-12"),
+    ("Fiji Standard Time", "Pacific/Fiji"),
+    ("Chatham Islands Standard Time", "Pacific/Chatham"),
+    ("UTC+13", "Etc/GMT-13"),
+    ("Tonga Standard Time", "Pacific/Tongatapu"),
+    ("Samoa Standard Time", "Pacific/Apia"),
+    ("Line Islands Standard Time", "Pacific/Kiritimati"),
+];
+
+/// Resolve a TZID to an IANA zone without looking at VTIMEZONE definitions
+///
+/// Accepts IANA names, Windows zone names (e.g. `Pacific Standard Time`) and
+/// vendor-prefixed IDs ending in an IANA name (e.g.
+/// `/mozilla.org/20050126_1/America/New_York`).
+#[must_use]
+pub fn resolve_iana(tzid: &str) -> Option<Tz> {
+    let tzid = tzid.trim().trim_matches('"');
+
+    if let Ok(tz) = tzid.parse::<Tz>() {
+        return Some(tz);
+    }
+
+    if let Some((_, iana)) = WINDOWS_ZONES
+        .iter()
+        .find(|(windows, _)| windows.eq_ignore_ascii_case(tzid))
+    {
+        return iana.parse::<Tz>().ok();
+    }
+
+    // Try the trailing path segments of vendor-prefixed IDs
+    let segments: Vec<&str> = tzid.split('/').filter(|s| !s.is_empty()).collect();
+    (1..segments.len())
+        .map(|skip| segments[skip..].join("/"))
+        .find_map(|candidate| candidate.parse::<Tz>().ok())
+}
+
+/// Timezones defined by the VTIMEZONE components of a calendar object
+///
+/// Used to resolve TZIDs that are neither IANA nor Windows names. Zones are
+/// keyed by TZID.
+#[derive(Debug, Clone, Default)]
+pub struct TimezoneRegistry {
+    zones: HashMap<String, VTimezone>,
+}
+
+impl TimezoneRegistry {
+    /// Collect the VTIMEZONE definitions of a parsed calendar
+    #[must_use]
+    pub fn from_calendar(calendar: &Calendar) -> Self {
+        let zones = calendar
+            .components
+            .iter()
+            .filter_map(|component| match component {
+                CalendarComponent::Other(other)
+                    if other.component_kind().eq_ignore_ascii_case("VTIMEZONE") =>
+                {
+                    let tzid = other.property_value("TZID")?.to_string();
+                    Some((tzid, VTimezone::from_component(other)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Self { zones }
+    }
+
+    /// Convert a local time in the zone named `tzid` to UTC
+    ///
+    /// Windows and vendor-prefixed names are mapped to IANA zones first; the
+    /// calendar's own VTIMEZONE definition is used otherwise. Returns `None`
+    /// when the TZID cannot be resolved.
+    #[must_use]
+    pub fn to_utc(&self, tzid: &str, local: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        if let Some(tz) = resolve_iana(tzid) {
+            return tz
+                .from_local_datetime(local)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc));
+        }
+
+        self.zones.get(tzid)?.to_utc(local)
+    }
+
+    /// The VTIMEZONE definition of a TZID that doesn't name an IANA zone
+    ///
+    /// Returns `None` for TZIDs [`resolve_iana`] understands, as well as for
+    /// unknown ones.
+    #[must_use]
+    pub fn custom_zone(&self, tzid: &str) -> Option<VTimezone> {
+        if resolve_iana(tzid).is_some() {
+            return None;
+        }
+
+        self.zones.get(tzid).cloned()
+    }
+}
+
+/// A VTIMEZONE: the STANDARD and DAYLIGHT observances of one zone
+///
+/// Kept with events whose TZID only the calendar itself defines, so their
+/// series can be expanded in it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VTimezone {
+    observances: Vec<Observance>,
+}
+
+/// One STANDARD or DAYLIGHT sub-component of a VTIMEZONE
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct Observance {
+    /// First onset, in local time before the transition
+    start: NaiveDateTime,
+    /// UTC offsets before and after the transition, in seconds
+    offset_from: i32,
+    offset_to: i32,
+    rrule: Option<String>,
+    rdates: Vec<NaiveDateTime>,
+}
+
+impl VTimezone {
+    /// Convert a local time in this zone to UTC
+    #[must_use]
+    pub fn to_utc(&self, local: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        self.offset_at(local)?
+            .from_local_datetime(local)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Convert a UTC time to local time in this zone
+    ///
+    /// Tries the offsets of every observance and keeps the one that converts
+    /// back to `utc`.
+    #[must_use]
+    pub fn to_local(&self, utc: &DateTime<Utc>) -> Option<NaiveDateTime> {
+        self.observances
+            .iter()
+            .flat_map(|observance| [observance.offset_to, observance.offset_from])
+            .filter_map(|offset| {
+                utc.naive_utc()
+                    .checked_add_signed(chrono::Duration::seconds(i64::from(offset)))
+            })
+            .find(|local| self.to_utc(local).as_ref() == Some(utc))
+    }
+
+    fn from_component(component: &impl Component) -> Self {
+        let observances = component
+            .components()
+            .iter()
+            .filter(|child| {
+                let kind = child.component_kind();
+                kind.eq_ignore_ascii_case("STANDARD") || kind.eq_ignore_ascii_case("DAYLIGHT")
+            })
+            .filter_map(Observance::from_component)
+            .collect();
+
+        Self { observances }
+    }
+
+    /// The UTC offset in effect at a local time
+    ///
+    /// This is the offset of the observance with the latest onset at or
+    /// before `local`; times before every onset use the earliest observance's
+    /// offset before its transition.
+    fn offset_at(&self, local: &NaiveDateTime) -> Option<FixedOffset> {
+        self.observances
+            .iter()
+            .filter_map(|observance| {
+                observance
+                    .last_onset_at_or_before(local)
+                    .map(|onset| (onset, observance.offset_to))
+            })
+            .max_by_key(|(onset, _)| *onset)
+            .map(|(_, offset)| offset)
+            .or_else(|| {
+                self.observances
+                    .iter()
+                    .min_by_key(|observance| observance.start)
+                    .map(|observance| observance.offset_from)
+            })
+            .and_then(FixedOffset::east_opt)
+    }
+}
+
+impl Observance {
+    fn from_component(component: &impl Component) -> Option<Self> {
+        let start = parse_local_datetime(component.property_value("DTSTART")?)?;
+        let offset_from =
+            parse_utc_offset(component.property_value("TZOFFSETFROM")?)?.local_minus_utc();
+        let offset_to =
+            parse_utc_offset(component.property_value("TZOFFSETTO")?)?.local_minus_utc();
+        let rrule = component.property_value("RRULE").map(String::from);
+
+        let rdates = component
+            .multi_properties()
+            .get("RDATE")
+            .into_iter()
+            .flatten()
+            .flat_map(|property| property.value().split(','))
+            .filter_map(|value| parse_local_datetime(value.trim()))
+            .collect();
+
+        Some(Self {
+            start,
+            offset_from,
+            offset_to,
+            rrule,
+            rdates,
+        })
+    }
+
+    /// The latest onset of this observance at or before `local`
+    fn last_onset_at_or_before(&self, local: &NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.start > *local {
+            return None;
+        }
+
+        let mut last = Some(self.start);
+
+        // Onsets are wall-clock times, so expand the rule as if they were UTC
+        if let Some(rrule_str) = &self.rrule {
+            let dtstart = rrule::Tz::UTC.from_utc_datetime(&self.start);
+            match parse_rrule(rrule_str, dtstart) {
+                Ok(rrule_set) => {
+                    for onset in &rrule_set {
+                        let onset = onset.naive_utc();
+                        if onset > *local {
+                            break;
+                        }
+                        last = Some(onset);
+                    }
+                }
+                Err(e) => debug!("Ignoring VTIMEZONE RRULE {}: {}", rrule_str, e),
+            }
+        }
+
+        self.rdates
+            .iter()
+            .filter(|rdate| *rdate <= local)
+            .copied()
+            .chain(last)
+            .max()
+    }
+}
+
+/// Parse a local DATE-TIME value (`YYYYMMDDTHHMMSS`, a trailing Z is ignored)
+fn parse_local_datetime(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S").ok()
+}
+
+/// Parse a UTC offset (`+HHMM` or `+HHMMSS`)
+fn parse_utc_offset(value: &str) -> Option<FixedOffset> {
+    let value = value.trim();
+    let sign = match value.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = &value[1..];
+    if !(digits.len() == 4 || digits.len() == 6) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let hours: i32 = digits[0..2].parse().ok()?;
+    let minutes: i32 = digits[2..4].parse().ok()?;
+    let seconds: i32 = digits.get(4..6).map_or(Some(0), |s| s.parse().ok())?;
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const CUSTOM_ZONE: &str = r"BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VTIMEZONE
+TZID:Custom Pacific
+BEGIN:STANDARD
+DTSTART:16011104T020000
+RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=11
+TZOFFSETFROM:-0700
+TZOFFSETTO:-0800
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:16010311T020000
+RRULE:FREQ=YEARLY;BYDAY=2SU;BYMONTH=3
+TZOFFSETFROM:-0800
+TZOFFSETTO:-0700
+END:DAYLIGHT
+END:VTIMEZONE
+BEGIN:VTIMEZONE
+TZID:Fixed Plus Five Thirty
+BEGIN:STANDARD
+DTSTART:19700101T000000
+TZOFFSETFROM:+0530
+TZOFFSETTO:+0530
+END:STANDARD
+END:VTIMEZONE
+END:VCALENDAR";
+
+    fn local(value: &str) -> NaiveDateTime {
+        parse_local_datetime(value).unwrap()
+    }
+
+    #[test]
+    fn test_windows_zones_resolve() {
+        for (windows, iana) in WINDOWS_ZONES {
+            assert!(
+                resolve_iana(windows).is_some(),
+                "{windows} -> {iana} is not a known IANA zone"
+            );
+        }
+        assert_eq!(
+            resolve_iana("Pacific Standard Time"),
+            Some(chrono_tz::America::Los_Angeles)
+        );
+        assert_eq!(
+            resolve_iana("W. Europe Standard Time"),
+            Some(chrono_tz::Europe::Berlin)
+        );
+    }
+
+    #[test]
+    fn test_resolve_vendor_prefixed_tzid() {
+        assert_eq!(
+            resolve_iana("/mozilla.org/20050126_1/America/New_York"),
+            Some(chrono_tz::America::New_York)
+        );
+        assert_eq!(
+            resolve_iana("/softwarestudio.org/Olson_20011030_5/Europe/Paris"),
+            Some(chrono_tz::Europe::Paris)
+        );
+        assert_eq!(resolve_iana("Not A Zone"), None);
+    }
+
+    #[test]
+    fn test_registry_uses_vtimezone_rules() {
+        let calendar = CUSTOM_ZONE.parse::<Calendar>().unwrap();
+        let registry = TimezoneRegistry::from_calendar(&calendar);
+
+        // Summer: daylight time (UTC-7)
+        assert_eq!(
+            registry.to_utc("Custom Pacific", &local("20260715T090000")),
+            Some(Utc.with_ymd_and_hms(2026, 7, 15, 16, 0, 0).unwrap())
+        );
+        // Winter: standard time (UTC-8)
+        assert_eq!(
+            registry.to_utc("Custom Pacific", &local("20260115T090000")),
+            Some(Utc.with_ymd_and_hms(2026, 1, 15, 17, 0, 0).unwrap())
+        );
+        // Just after the 2026 spring transition (March 8)
+        assert_eq!(
+            registry.to_utc("Custom Pacific", &local("20260308T120000")),
+            Some(Utc.with_ymd_and_hms(2026, 3, 8, 19, 0, 0).unwrap())
+        );
+        assert_eq!(
+            registry.to_utc("Fixed Plus Five Thirty", &local("20260101T120000")),
+            Some(Utc.with_ymd_and_hms(2026, 1, 1, 6, 30, 0).unwrap())
+        );
+        assert_eq!(
+            registry.to_utc("Unknown Zone", &local("20260101T120000")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_registry_prefers_windows_mapping() {
+        let registry = TimezoneRegistry::default();
+        assert_eq!(
+            registry.to_utc("Eastern Standard Time", &local("20260715T090000")),
+            Some(Utc.with_ymd_and_hms(2026, 7, 15, 13, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("-0800"), FixedOffset::west_opt(8 * 3600));
+        assert_eq!(
+            parse_utc_offset("+053000"),
+            FixedOffset::east_opt(5 * 3600 + 30 * 60)
+        );
+        assert_eq!(parse_utc_offset("0800"), None);
+        assert_eq!(parse_utc_offset("+8"), None);
+    }
+}
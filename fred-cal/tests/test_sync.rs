@@ -8,10 +8,11 @@
 //! These tests verify the SyncManager's ability to synchronize calendar data
 //! with a CalDAV server using mocked HTTP responses.
 
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, Timelike, Utc};
 use fast_dav_rs::CalDavClient;
 use fred_cal::cache::CacheManager;
-use fred_cal::sync::SyncManager;
+use fred_cal::models::{EventStatus, JournalStatus, TodoStatus};
+use fred_cal::sync::{SyncManager, SyncWindow};
 use std::sync::Arc;
 use tempfile::tempdir;
 use wiremock::matchers::{method, path};
@@ -29,8 +30,8 @@ fn setup_rustls() {
     });
 }
 
-/// Generate a datetime within the recurrence expansion window (default: 730 days forward)
-/// This ensures recurring events will expand properly in tests.
+/// Generate a datetime relative to now
+/// This keeps recurring test events inside the ranges queried by the tests.
 fn test_date_in_future(days_from_now: i64) -> DateTime<Utc> {
     Utc::now() + Duration::days(days_from_now)
 }
@@ -82,6 +83,10 @@ async fn setup_mock_caldav_server(mock_server: &MockServer) {
         <c:calendar-home-set>
           <d:href>/calendars/user/</d:href>
         </c:calendar-home-set>
+        <c:calendar-user-address-set>
+          <d:href>mailto:user@example.com</d:href>
+          <d:href>/principals/user/</d:href>
+        </c:calendar-user-address-set>
       </d:prop>
       <d:status>HTTP/1.1 200 OK</d:status>
     </d:propstat>
@@ -98,6 +103,57 @@ async fn setup_mock_caldav_server(mock_server: &MockServer) {
         .await;
 }
 
+/// Set up a server supporting sync-collection with one calendar at
+/// `/calendars/user/cal/`
+///
+/// REPORTs on the calendar are answered by [`mount_calendar_report`] mocks.
+async fn setup_incremental_calendar(mock_server: &MockServer) {
+    setup_mock_caldav_server(mock_server).await;
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Calendar</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(mock_server)
+        .await;
+
+    // Answer the sync-collection probe so incremental sync is used
+    Mock::given(method("REPORT"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(207))
+        .mount(mock_server)
+        .await;
+}
+
+/// Answer one REPORT on `/calendars/user/cal/` with `body`
+///
+/// Request bodies are compressed, so REPORTs can't be told apart by content;
+/// each mock answers once, in the order they were mounted.
+async fn mount_calendar_report(mock_server: &MockServer, body: &str) {
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(body))
+        .up_to_n_times(1)
+        .mount(mock_server)
+        .await;
+}
+
 /// Test basic synchronization with a single event
 #[tokio::test]
 async fn test_basic_sync_single_event() -> Result<(), Box<dyn std::error::Error>> {
@@ -545,6 +601,7 @@ UID:evt1
 DTSTART:{}
 DTEND:{}
 SUMMARY:Morning Standup
+X-FRED-NOTE:kept
 END:VEVENT
 END:VCALENDAR</c:calendar-data>
       </d:prop>
@@ -580,6 +637,15 @@ END:VCALENDAR</c:calendar-data>
         let calendar_data = data.read().await;
         assert_eq!(calendar_data.events.len(), 1);
         assert_eq!(calendar_data.events[0].summary, "Morning Standup");
+
+        // The original source is kept, including unmapped properties
+        let object = calendar_data
+            .object_for_uid("evt1")
+            .ok_or("missing calendar object")?;
+        assert_eq!(object.href, "/calendars/user/work/event1.ics");
+        assert_eq!(object.calendar_url, "/calendars/user/work/");
+        assert!(object.etag.as_deref().is_some_and(|e| e.contains("e1")));
+        assert!(object.ics.contains("X-FRED-NOTE:kept"));
     }
 
     Ok(())
@@ -1043,20 +1109,14 @@ END:VCALENDAR</c:calendar-data>
     let data = sync_manager.data();
     let calendar_data = data.read().await;
 
+    // Only the master is stored; instances are expanded at query time
+    assert_eq!(calendar_data.events.len(), 1);
+
     // Should have 5 instances of the recurring event
-    assert_eq!(calendar_data.events.len(), 5);
-    assert!(
-        calendar_data
-            .events
-            .iter()
-            .all(|e| e.summary == "Daily Standup")
-    );
-    assert!(
-        calendar_data
-            .events
-            .iter()
-            .all(|e| e.uid == "recurring-standup")
-    );
+    let instances = calendar_data.events_in_range(Utc::now(), Utc::now() + Duration::days(60));
+    assert_eq!(instances.len(), 5);
+    assert!(instances.iter().all(|e| e.summary == "Daily Standup"));
+    assert!(instances.iter().all(|e| e.uid == "recurring-standup"));
 
     Ok(())
 }
@@ -1142,11 +1202,12 @@ END:VCALENDAR</c:calendar-data>
     let calendar_data = data.read().await;
 
     // Should have 3 instances (4 - 1 excluded by EXDATE)
-    assert_eq!(calendar_data.events.len(), 3);
+    let instances = calendar_data.events_in_range(Utc::now(), Utc::now() + Duration::days(60));
+    assert_eq!(instances.len(), 3);
 
     // Verify that the excluded date is not in the results
     let excluded_date = weekly_start + Duration::days(7);
-    for event in &calendar_data.events {
+    for event in &instances {
         assert_ne!(
             event.start, excluded_date,
             "Event with EXDATE should be excluded from instances"
@@ -1156,6 +1217,135 @@ END:VCALENDAR</c:calendar-data>
     Ok(())
 }
 
+/// Test recurring event with moved and cancelled overrides (RECURRENCE-ID)
+#[tokio::test]
+async fn test_recurring_event_with_overrides() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_mock_caldav_server(&mock_server).await;
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Calendar</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let weekly_start = test_date_in_future(7)
+        .with_nanosecond(0)
+        .ok_or("invalid test date")?;
+    let weekly_end = weekly_start + Duration::hours(1);
+    let moved_original = weekly_start + Duration::days(7);
+    let moved_start = moved_original + Duration::hours(3);
+    let cancelled_original = weekly_start + Duration::days(14);
+
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/standup.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"s1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:standup
+DTSTART:{}
+DTEND:{}
+SUMMARY:Standup
+RRULE:FREQ=WEEKLY;COUNT=4
+END:VEVENT
+BEGIN:VEVENT
+UID:standup
+RECURRENCE-ID:{}
+DTSTART:{}
+DTEND:{}
+SUMMARY:Standup (moved)
+END:VEVENT
+BEGIN:VEVENT
+UID:standup
+RECURRENCE-ID:{}
+DTSTART:{}
+DTEND:{}
+SUMMARY:Standup
+STATUS:CANCELLED
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+            format_ical_datetime(weekly_start),
+            format_ical_datetime(weekly_end),
+            format_ical_datetime(moved_original),
+            format_ical_datetime(moved_start),
+            format_ical_datetime(moved_start + Duration::hours(1)),
+            format_ical_datetime(cancelled_original),
+            format_ical_datetime(cancelled_original),
+            format_ical_datetime(cancelled_original + Duration::hours(1)),
+        )))
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    sync_manager.sync().await?;
+
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+
+    // The master and both overrides are stored as they are
+    assert_eq!(calendar_data.events.len(), 3);
+
+    // 4 occurrences, one moved and one cancelled
+    let instances = calendar_data.events_in_range(Utc::now(), Utc::now() + Duration::days(60));
+    assert_eq!(instances.len(), 3);
+
+    let moved = instances
+        .iter()
+        .find(|e| e.summary == "Standup (moved)")
+        .ok_or("moved occurrence should be present")?;
+    assert_eq!(moved.start, moved_start);
+    assert_eq!(moved.recurrence_id, Some(moved_original));
+
+    for event in &instances {
+        assert_ne!(
+            event.start, moved_original,
+            "Original slot should be replaced"
+        );
+        assert_ne!(
+            event.start, cancelled_original,
+            "Cancelled occurrence should be dropped"
+        );
+    }
+
+    Ok(())
+}
+
 /// Test batch processing with large number of events
 #[tokio::test]
 async fn test_batch_processing_large_calendar() -> Result<(), Box<dyn std::error::Error>> {
@@ -1788,7 +1978,7 @@ END:VCALENDAR</c:calendar-data>
     );
     assert_eq!(todo.priority, Some(1));
     assert_eq!(todo.percent_complete, Some(60));
-    assert_eq!(todo.status, "InProcess".to_string());
+    assert_eq!(todo.status, TodoStatus::InProcess);
     assert!(todo.due.is_some());
     assert!(todo.start.is_some());
     assert!(todo.completed.is_some());
@@ -1932,9 +2122,1306 @@ END:VCALENDAR</c:calendar-data>
         .iter()
         .find(|e| e.uid == "cancelled-event");
 
-    assert_eq!(confirmed.unwrap().status, Some("Confirmed".to_string()));
-    assert_eq!(tentative.unwrap().status, Some("Tentative".to_string()));
-    assert_eq!(cancelled.unwrap().status, Some("Cancelled".to_string()));
+    assert_eq!(confirmed.unwrap().status, Some(EventStatus::Confirmed));
+    assert_eq!(tentative.unwrap().status, Some(EventStatus::Tentative));
+    assert_eq!(cancelled.unwrap().status, Some(EventStatus::Cancelled));
+
+    Ok(())
+}
+
+/// Test that attendees are synced and the user's own PARTSTAT is detected
+#[tokio::test]
+async fn test_sync_attendees_and_own_partstat() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_mock_caldav_server(&mock_server).await;
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/work/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Work Calendar</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let start = test_date_in_future(10);
+    let end = start + Duration::hours(1);
+
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/work/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/work/review.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"etag1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Test//Test//EN
+BEGIN:VEVENT
+UID:review@example.com
+DTSTART:{}
+DTEND:{}
+SUMMARY:Design Review
+ORGANIZER;CN=Alice:mailto:alice@example.com
+ATTENDEE;CN=Alice;PARTSTAT=ACCEPTED;ROLE=CHAIR:mailto:alice@example.com
+ATTENDEE;CN=Me;PARTSTAT=DECLINED;RSVP=TRUE:mailto:USER@example.com
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+            format_ical_datetime(start),
+            format_ical_datetime(end)
+        )))
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    sync_manager.sync().await?;
+
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    assert_eq!(calendar_data.events.len(), 1);
+
+    let event = &calendar_data.events[0];
+    assert_eq!(
+        event.organizer.as_ref().map(|o| o.address.as_str()),
+        Some("mailto:alice@example.com")
+    );
+    assert_eq!(event.attendees.len(), 2);
+    assert_eq!(event.attendees[0].role, "CHAIR");
+    assert!(event.attendees[1].rsvp);
+    assert_eq!(event.own_partstat.as_deref(), Some("DECLINED"));
+    assert!(event.is_declined());
+
+    Ok(())
+}
+
+/// Test that VJOURNAL notes are synced and cached
+#[tokio::test]
+async fn test_sync_journals() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_mock_caldav_server(&mock_server).await;
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/notes/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Notes</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let note_date = test_date_in_future(2);
+
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/notes/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/notes/standup-notes.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"j1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Test//Test//EN
+BEGIN:VJOURNAL
+UID:standup-notes
+DTSTART;VALUE=DATE:{}
+SUMMARY:Standup notes
+DESCRIPTION:Shipped the release
+CATEGORIES:Daily
+STATUS:FINAL
+END:VJOURNAL
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+            format_ical_date(note_date)
+        )))
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache_path = temp_dir.path().to_path_buf();
+
+    {
+        let cache = CacheManager::new_with_path(cache_path.clone())?;
+        let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+        let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+        sync_manager.sync().await?;
+
+        let data = sync_manager.data();
+        let calendar_data = data.read().await;
+
+        // Every component query returns the same resource; it is parsed once
+        assert_eq!(calendar_data.journals.len(), 1);
+        assert!(calendar_data.events.is_empty());
+        assert!(calendar_data.todos.is_empty());
+
+        let journal = &calendar_data.journals[0];
+        assert_eq!(journal.uid, "standup-notes");
+        assert_eq!(journal.summary, "Standup notes");
+        assert_eq!(journal.description.as_deref(), Some("Shipped the release"));
+        assert_eq!(journal.start_date, Some(note_date.date_naive()));
+        assert_eq!(journal.status, Some(JournalStatus::Final));
+        assert_eq!(journal.categories, vec!["Daily"]);
+        assert_eq!(journal.calendar_name, "Notes");
+    }
+
+    // Journals are loaded back from the cache
+    let cache = CacheManager::new_with_path(cache_path)?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+    let data = sync_manager.data();
+    assert_eq!(data.read().await.journals.len(), 1);
+
+    Ok(())
+}
+
+/// Test that sync-collection deletions are matched by resource href, not UID
+#[tokio::test]
+async fn test_incremental_deletion_by_href() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_incremental_calendar(&mock_server).await;
+
+    // REPORTs are answered in order: the initial sync-collection, the
+    // multiget of its changes, then the next sync-collection
+    // Initial sync-collection: two resources whose names aren't their UIDs
+    mount_calendar_report(
+        &mock_server,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/calendars/user/cal/0F3A9C.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"a1"</d:getetag></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/calendars/user/cal/7D21B4.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"b1"</d:getetag></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:sync-token>token-1</d:sync-token>
+</d:multistatus>"#,
+    )
+    .await;
+
+    let start = test_date_in_future(30);
+    let end = start + Duration::hours(1);
+    mount_calendar_report(
+        &mock_server,
+        &format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/0F3A9C.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"a1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:planning@example.com
+DTSTART:{start}
+DTEND:{end}
+SUMMARY:Planning
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/calendars/user/cal/7D21B4.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"b1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:review@example.com
+DTSTART:{start}
+DTEND:{end}
+SUMMARY:Review
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+            start = format_ical_datetime(start),
+            end = format_ical_datetime(end)
+        ),
+    )
+    .await;
+
+    // Next sync-collection: the first resource was deleted
+    mount_calendar_report(
+        &mock_server,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/calendars/user/cal/0F3A9C.ics</d:href>
+    <d:status>HTTP/1.1 404 Not Found</d:status>
+  </d:response>
+  <d:sync-token>token-2</d:sync-token>
+</d:multistatus>"#,
+    )
+    .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    sync_manager.sync().await?;
+    {
+        let data = sync_manager.data();
+        let calendar_data = data.read().await;
+        assert_eq!(calendar_data.events.len(), 2);
+        let planning = calendar_data
+            .events
+            .iter()
+            .find(|e| e.uid == "planning@example.com")
+            .ok_or("planning event missing")?;
+        assert_eq!(
+            planning.href.as_deref(),
+            Some("/calendars/user/cal/0F3A9C.ics")
+        );
+    }
+
+    sync_manager.sync().await?;
+
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    assert_eq!(calendar_data.events.len(), 1);
+    assert_eq!(calendar_data.events[0].uid, "review@example.com");
+    assert!(
+        !calendar_data
+            .objects
+            .contains_key("/calendars/user/cal/0F3A9C.ics")
+    );
+    assert_eq!(
+        calendar_data.sync_tokens.get("/calendars/user/cal/"),
+        Some(&"token-2".to_string())
+    );
+
+    Ok(())
+}
+
+/// Test that a truncated sync-collection response is followed up to the end
+#[tokio::test]
+async fn test_incremental_sync_follows_truncated_pages() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_incremental_calendar(&mock_server).await;
+
+    let start = test_date_in_future(30);
+    let end = start + Duration::hours(1);
+    let multiget = |href: &str, uid: &str| {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>{href}</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:{uid}
+DTSTART:{start}
+DTEND:{end}
+SUMMARY:{uid}
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+            start = format_ical_datetime(start),
+            end = format_ical_datetime(end)
+        )
+    };
+
+    // First page, marked as truncated with 507 on the request-URI
+    mount_calendar_report(
+        &mock_server,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/calendars/user/cal/first.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"1"</d:getetag></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:status>HTTP/1.1 507 Insufficient Storage</d:status>
+  </d:response>
+  <d:sync-token>page-2</d:sync-token>
+</d:multistatus>"#,
+    )
+    .await;
+    mount_calendar_report(
+        &mock_server,
+        &multiget("/calendars/user/cal/first.ics", "first"),
+    )
+    .await;
+
+    // Second and last page
+    mount_calendar_report(
+        &mock_server,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/calendars/user/cal/second.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"1"</d:getetag></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:sync-token>done</d:sync-token>
+</d:multistatus>"#,
+    )
+    .await;
+    mount_calendar_report(
+        &mock_server,
+        &multiget("/calendars/user/cal/second.ics", "second"),
+    )
+    .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    sync_manager.sync().await?;
+
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    let mut uids: Vec<&str> = calendar_data
+        .events
+        .iter()
+        .map(|e| e.uid.as_str())
+        .collect();
+    uids.sort_unstable();
+    assert_eq!(uids, vec!["first", "second"]);
+    assert_eq!(
+        calendar_data.sync_tokens.get("/calendars/user/cal/"),
+        Some(&"done".to_string())
+    );
+
+    Ok(())
+}
+
+/// Test that a rejected sync token is dropped and the calendar resynced
+#[tokio::test]
+async fn test_incremental_sync_recovers_from_invalid_token()
+-> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_incremental_calendar(&mock_server).await;
+
+    let start = test_date_in_future(30);
+    let end = start + Duration::hours(1);
+    let listing = |hrefs: &[&str], token: &str| {
+        let responses: String = hrefs
+            .iter()
+            .map(|href| {
+                format!(
+                    r#"
+  <d:response>
+    <d:href>{href}</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"1"</d:getetag></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>"#
+                )
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:">{responses}
+  <d:sync-token>{token}</d:sync-token>
+</d:multistatus>"#
+        )
+    };
+    let multiget = |resources: &[(&str, &str)]| {
+        let responses: String = resources
+            .iter()
+            .map(|(href, uid)| {
+                format!(
+                    r#"
+  <d:response>
+    <d:href>{href}</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:{uid}
+DTSTART:{start}
+DTEND:{end}
+SUMMARY:{uid}
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>"#,
+                    start = format_ical_datetime(start),
+                    end = format_ical_datetime(end)
+                )
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">{responses}
+</d:multistatus>"#
+        )
+    };
+
+    // First sync
+    mount_calendar_report(
+        &mock_server,
+        &listing(
+            &[
+                "/calendars/user/cal/kept.ics",
+                "/calendars/user/cal/gone.ics",
+            ],
+            "old-token",
+        ),
+    )
+    .await;
+    mount_calendar_report(
+        &mock_server,
+        &multiget(&[
+            ("/calendars/user/cal/kept.ics", "kept"),
+            ("/calendars/user/cal/gone.ics", "gone"),
+        ]),
+    )
+    .await;
+
+    // Second sync: the token is rejected, then the calendar is listed again
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(403).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:error xmlns:d="DAV:"><d:valid-sync-token/></d:error>"#,
+        ))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    mount_calendar_report(
+        &mock_server,
+        &listing(&["/calendars/user/cal/kept.ics"], "new-token"),
+    )
+    .await;
+    mount_calendar_report(
+        &mock_server,
+        &multiget(&[("/calendars/user/cal/kept.ics", "kept")]),
+    )
+    .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    sync_manager.sync().await?;
+    assert_eq!(sync_manager.data().read().await.events.len(), 2);
+
+    sync_manager.sync().await?;
+
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    assert_eq!(calendar_data.events.len(), 1);
+    assert_eq!(calendar_data.events[0].uid, "kept");
+    assert_eq!(
+        calendar_data.sync_tokens.get("/calendars/user/cal/"),
+        Some(&"new-token".to_string())
+    );
+    assert_eq!(sync_manager.sync_token_resets(), 1);
+
+    Ok(())
+}
+
+/// Test that a calendar whose CTag hasn't changed is not fetched again
+#[tokio::test]
+async fn test_unchanged_ctag_skips_calendar() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_mock_caldav_server(&mock_server).await;
+
+    let calendar_list = |ctag: &str| {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/">
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Calendar</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+        <cs:getctag>{ctag}</cs:getctag>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#
+        )
+    };
+
+    // Each sync lists the calendars and asks for their CTags: the first two
+    // syncs see the same CTag, the third a new one
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(calendar_list("1")))
+        .up_to_n_times(4)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(calendar_list("2")))
+        .mount(&mock_server)
+        .await;
+
+    let start = test_date_in_future(10);
+    let end = start + Duration::hours(1);
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/e1.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"e1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:event1
+DTSTART:{}
+DTEND:{}
+SUMMARY:Event 1
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+            format_ical_datetime(start),
+            format_ical_datetime(end)
+        )))
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    let calendar_reports = || async {
+        mock_server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| r.method.as_str() == "REPORT" && r.url.path() == "/calendars/user/cal/")
+            .count()
+    };
+
+    sync_manager.sync().await?;
+    let first = calendar_reports().await;
+    assert!(first > 0);
+    assert_eq!(
+        sync_manager
+            .data()
+            .read()
+            .await
+            .ctags
+            .get("/calendars/user/cal/")
+            .map(String::as_str),
+        Some("1")
+    );
+
+    // Unchanged CTag: the calendar is skipped and its events kept
+    sync_manager.sync().await?;
+    assert_eq!(calendar_reports().await, first);
+    assert_eq!(sync_manager.data().read().await.events.len(), 1);
+
+    // New CTag: the calendar is fetched again
+    sync_manager.sync().await?;
+    assert_eq!(calendar_reports().await, first * 2);
+
+    Ok(())
+}
+
+/// Test that a calendar isn't marked unchanged after a partly failed sync
+#[tokio::test]
+async fn test_partial_sync_keeps_ctag_unset() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_mock_caldav_server(&mock_server).await;
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/">
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Calendar</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+        <cs:getctag>1</cs:getctag>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let start = test_date_in_future(10);
+    let end = start + Duration::hours(1);
+    let events = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/e1.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"e1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:event1
+DTSTART:{}
+DTEND:{}
+SUMMARY:Event 1
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        format_ical_datetime(start),
+        format_ical_datetime(end)
+    );
+
+    // The first full sync queries events, todos and journals in turn; the
+    // todo query fails
+    mount_calendar_report(&mock_server, &events).await;
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(events))
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    let ctag = || async {
+        sync_manager
+            .data()
+            .read()
+            .await
+            .ctags
+            .get("/calendars/user/cal/")
+            .cloned()
+    };
+
+    sync_manager.sync().await?;
+    assert_eq!(sync_manager.data().read().await.events.len(), 1);
+    assert_eq!(ctag().await, None);
+
+    // The calendar is fetched again despite the unchanged CTag
+    sync_manager.sync().await?;
+    assert_eq!(ctag().await.as_deref(), Some("1"));
+
+    Ok(())
+}
+
+/// Test that a calendar offering only events isn't queried for other components
+#[tokio::test]
+async fn test_event_only_calendar_stores_ctag() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_mock_caldav_server(&mock_server).await;
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/">
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Calendar</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+        <c:supported-calendar-component-set>
+          <c:comp name="VEVENT"/>
+        </c:supported-calendar-component-set>
+        <cs:getctag>1</cs:getctag>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let start = test_date_in_future(10);
+    let end = start + Duration::hours(1);
+    mount_calendar_report(
+        &mock_server,
+        &format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/e1.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"e1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:event1
+DTSTART:{}
+DTEND:{}
+SUMMARY:Event 1
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+            format_ical_datetime(start),
+            format_ical_datetime(end)
+        ),
+    )
+    .await;
+
+    // Like some servers, reject queries for components the calendar lacks
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(403))
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    sync_manager.sync().await?;
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    assert_eq!(calendar_data.events.len(), 1);
+    assert_eq!(
+        calendar_data
+            .ctags
+            .get("/calendars/user/cal/")
+            .map(String::as_str),
+        Some("1")
+    );
+    drop(calendar_data);
+
+    // Only the event query was sent
+    let reports = mock_server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| r.method.as_str() == "REPORT" && r.url.path() == "/calendars/user/cal/")
+        .count();
+    assert_eq!(reports, 1);
+
+    Ok(())
+}
+
+/// Test that servers without sync-collection only fetch new and changed resources
+#[tokio::test]
+async fn test_etag_diff_sync() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_mock_caldav_server(&mock_server).await;
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Calendar</d:displayname>
+        <d:resourcetype>
+          <d:collection/>
+          <c:calendar/>
+        </d:resourcetype>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let start = test_date_in_future(10);
+    let end = start + Duration::hours(1);
+    let resources = |resources: &[(&str, &str, &str)]| {
+        let responses: String = resources
+            .iter()
+            .map(|(name, etag, summary)| {
+                format!(
+                    r#"
+  <d:response>
+    <d:href>/calendars/user/cal/{name}.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"{etag}"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:{name}
+DTSTART:{start}
+DTEND:{end}
+SUMMARY:{summary}
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>"#,
+                    start = format_ical_datetime(start),
+                    end = format_ical_datetime(end)
+                )
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">{responses}
+</d:multistatus>"#
+        )
+    };
+
+    // First sync: full calendar-query, once per component type
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(resources(&[
+            ("a", "a1", "A"),
+            ("b", "b1", "B"),
+            ("d", "d1", "D"),
+        ])))
+        .up_to_n_times(3)
+        .mount(&mock_server)
+        .await;
+
+    // Second sync: "a" is unchanged, "b" changed, "c" is new, "d" was deleted
+    Mock::given(method("PROPFIND"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(207).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/calendars/user/cal/</d:href>
+    <d:propstat>
+      <d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/calendars/user/cal/a.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"a1"</d:getetag><d:resourcetype/></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/calendars/user/cal/b.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"b2"</d:getetag><d:resourcetype/></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/calendars/user/cal/c.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"c1"</d:getetag><d:resourcetype/></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#,
+        ))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(
+            ResponseTemplate::new(207)
+                .set_body_string(resources(&[("b", "b2", "B updated"), ("c", "c1", "C")])),
+        )
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?);
+
+    sync_manager.sync().await?;
+    assert_eq!(sync_manager.data().read().await.events.len(), 3);
+
+    sync_manager.sync().await?;
+
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    let mut events: Vec<(&str, &str)> = calendar_data
+        .events
+        .iter()
+        .map(|e| (e.uid.as_str(), e.summary.as_str()))
+        .collect();
+    events.sort_unstable();
+    assert_eq!(events, vec![("a", "A"), ("b", "B updated"), ("c", "C")]);
+    assert_eq!(
+        calendar_data.objects["/calendars/user/cal/b.ics"]
+            .etag
+            .as_deref(),
+        Some("\"b2\"")
+    );
+
+    // Three calendar-queries for the first sync, one multiget for the second
+    let reports = mock_server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| r.method.as_str() == "REPORT" && r.url.path() == "/calendars/user/cal/")
+        .count();
+    assert_eq!(reports, 4);
+
+    Ok(())
+}
+
+/// Test that a sync window compares ETags within the window, even on a
+/// server with sync-collection, and drops resources that left it
+#[tokio::test]
+async fn test_sync_window_lists_resources_in_range() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_incremental_calendar(&mock_server).await;
+
+    let start = test_date_in_future(10);
+    let end = start + Duration::hours(1);
+    let event = |name: &str| {
+        format!(
+            r#"
+  <d:response>
+    <d:href>/calendars/user/cal/{name}.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"{name}1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:VEVENT
+UID:{name}
+DTSTART:{start}
+DTEND:{end}
+SUMMARY:{name}
+END:VEVENT
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>"#,
+            start = format_ical_datetime(start),
+            end = format_ical_datetime(end)
+        )
+    };
+    let multistatus = |responses: &str| {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">{responses}
+</d:multistatus>"#
+        )
+    };
+
+    // First sync: windowed calendar-query, once per component type
+    let both = multistatus(&(event("a") + &event("b")));
+    for _ in 0..3 {
+        mount_calendar_report(&mock_server, &both).await;
+    }
+
+    // Second sync: only "a" is still in the window, and it's unchanged
+    let listing = multistatus(
+        r#"
+  <d:response>
+    <d:href>/calendars/user/cal/a.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"a1"</d:getetag></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>"#,
+    );
+    mount_calendar_report(&mock_server, &listing).await;
+    for _ in 0..2 {
+        mount_calendar_report(&mock_server, &multistatus("")).await;
+    }
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let window = SyncWindow {
+        past_days: Some(365),
+        future_days: Some(730),
+    };
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?.with_sync_window(window));
+    assert_eq!(sync_manager.sync_window(), window);
+
+    sync_manager.sync().await?;
+    assert_eq!(sync_manager.data().read().await.events.len(), 2);
+
+    sync_manager.sync().await?;
+
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    let uids: Vec<&str> = calendar_data
+        .events
+        .iter()
+        .map(|e| e.uid.as_str())
+        .collect();
+    assert_eq!(uids, vec!["a"]);
+    assert!(
+        !calendar_data
+            .objects
+            .contains_key("/calendars/user/cal/b.ics")
+    );
+
+    // Three calendar-queries per sync, no sync-collection and no multiget
+    let reports = mock_server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| r.method.as_str() == "REPORT" && r.url.path() == "/calendars/user/cal/")
+        .count();
+    assert_eq!(reports, 6);
+
+    Ok(())
+}
+
+/// Test that a rejected journal query doesn't turn a windowed ETag sync into a full sync
+#[tokio::test]
+async fn test_sync_window_skips_rejected_component() -> Result<(), Box<dyn std::error::Error>> {
+    setup_rustls();
+
+    let mock_server = MockServer::start().await;
+    setup_incremental_calendar(&mock_server).await;
+
+    let start = test_date_in_future(10);
+    let end = start + Duration::hours(1);
+    let resource = |name: &str, component: &str| {
+        let times = if component == "VEVENT" {
+            format!(
+                "DTSTART:{}\nDTEND:{}",
+                format_ical_datetime(start),
+                format_ical_datetime(end)
+            )
+        } else {
+            format!("DTSTART:{}", format_ical_datetime(start))
+        };
+        format!(
+            r#"
+  <d:response>
+    <d:href>/calendars/user/cal/{name}.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"{name}1"</d:getetag>
+        <c:calendar-data>BEGIN:VCALENDAR
+VERSION:2.0
+BEGIN:{component}
+UID:{name}
+{times}
+SUMMARY:{name}
+END:{component}
+END:VCALENDAR</c:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>"#
+        )
+    };
+    let multistatus = |responses: &str| {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">{responses}
+</d:multistatus>"#
+        )
+    };
+
+    // First sync: events, todos and journals in the window
+    let events = resource("a", "VEVENT") + &resource("b", "VEVENT");
+    mount_calendar_report(&mock_server, &multistatus(&events)).await;
+    mount_calendar_report(&mock_server, &multistatus("")).await;
+    mount_calendar_report(&mock_server, &multistatus(&resource("j", "VJOURNAL"))).await;
+
+    // Second sync: "b" is gone and the journal query is rejected
+    mount_calendar_report(
+        &mock_server,
+        &multistatus(
+            r#"
+  <d:response>
+    <d:href>/calendars/user/cal/a.ics</d:href>
+    <d:propstat>
+      <d:prop><d:getetag>"a1"</d:getetag></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>"#,
+        ),
+    )
+    .await;
+    mount_calendar_report(&mock_server, &multistatus("")).await;
+    Mock::given(method("REPORT"))
+        .and(path("/calendars/user/cal/"))
+        .respond_with(ResponseTemplate::new(403))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+
+    let temp_dir = tempdir()?;
+    let cache = CacheManager::new_with_path(temp_dir.path().to_path_buf())?;
+    let client = CalDavClient::new(&mock_server.uri(), Some("user"), Some("pass"))?;
+    let window = SyncWindow {
+        past_days: Some(365),
+        future_days: Some(730),
+    };
+    let sync_manager = Arc::new(SyncManager::new(client, cache)?.with_sync_window(window));
+
+    sync_manager.sync().await?;
+    sync_manager.sync().await?;
+
+    // The event listing still applies, and the journal is kept
+    let data = sync_manager.data();
+    let calendar_data = data.read().await;
+    let uids: Vec<&str> = calendar_data
+        .events
+        .iter()
+        .map(|e| e.uid.as_str())
+        .collect();
+    assert_eq!(uids, vec!["a"]);
+    assert_eq!(calendar_data.journals.len(), 1);
+    assert!(
+        calendar_data
+            .objects
+            .contains_key("/calendars/user/cal/j.ics")
+    );
+    drop(calendar_data);
+
+    // No full sync after the rejected query
+    let reports = mock_server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| r.method.as_str() == "REPORT" && r.url.path() == "/calendars/user/cal/")
+        .count();
+    assert_eq!(reports, 6);
 
     Ok(())
 }